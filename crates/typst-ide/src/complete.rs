@@ -284,6 +284,11 @@ fn complete_math(ctx: &mut CompletionContext) -> bool {
         return false;
     }
 
+    // An attached label is not math: "$x$ <a|>".
+    if ctx.leaf.kind() == SyntaxKind::Label {
+        return false;
+    }
+
     // Start of an interpolated identifier: "#|".
     if ctx.leaf.kind() == SyntaxKind::Hash {
         ctx.from = ctx.cursor;
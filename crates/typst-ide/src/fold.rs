@@ -0,0 +1,193 @@
+use std::ops::Range;
+
+use typst::syntax::{ast, LinkedNode, Source, SyntaxKind};
+
+/// A region of a source file that an editor can fold.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fold {
+    /// The byte range of the region.
+    pub range: Range<usize>,
+    /// What kind of region this is.
+    pub kind: FoldKind,
+}
+
+/// A kind of foldable region.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FoldKind {
+    /// A heading up to the next heading of the same or a lower depth.
+    Heading,
+    /// A code or content block.
+    Block,
+    /// A raw block.
+    Raw,
+    /// A run of list, enumeration, or term list items.
+    List,
+    /// A block comment.
+    Comment,
+}
+
+/// Find the foldable regions of a source file, ordered by their start.
+///
+/// Only regions that span multiple lines are included.
+pub fn folding_ranges(source: &Source) -> Vec<Fold> {
+    let mut folds = vec![];
+    collect(&LinkedNode::new(source.root()), &mut folds);
+    folds.retain(|fold| {
+        source.byte_to_line(fold.range.start) != source.byte_to_line(fold.range.end)
+    });
+    folds.sort_by_key(|fold| fold.range.start);
+    folds
+}
+
+/// Collect the foldable regions in a node and its descendants.
+fn collect(node: &LinkedNode, folds: &mut Vec<Fold>) {
+    let kind = match node.kind() {
+        SyntaxKind::CodeBlock | SyntaxKind::ContentBlock => Some(FoldKind::Block),
+        SyntaxKind::Raw => Some(FoldKind::Raw),
+        SyntaxKind::BlockComment => Some(FoldKind::Comment),
+        SyntaxKind::Markup => {
+            let children: Vec<_> = node.children().collect();
+            folds.extend(
+                sections(&children)
+                    .into_iter()
+                    .map(|range| Fold { range, kind: FoldKind::Heading }),
+            );
+            lists(&children, folds);
+            None
+        }
+        _ => None,
+    };
+
+    if let Some(kind) = kind {
+        // An attached label is not part of the folded raw block.
+        let end = node
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::RawDelim)
+            .last()
+            .map_or(node.range().end, |delim| delim.range().end);
+        folds.push(Fold { range: node.offset()..end, kind });
+    }
+
+    for child in node.children() {
+        collect(&child, folds);
+    }
+}
+
+/// The ranges of the sections in a markup node, each spanning from a heading
+/// up to the next heading of the same or a lower depth.
+pub(crate) fn sections(children: &[LinkedNode]) -> Vec<Range<usize>> {
+    let mut sections = vec![];
+    for (i, child) in children.iter().enumerate() {
+        let Some(heading) = child.cast::<ast::Heading>() else { continue };
+        let depth = heading.depth();
+        let rest = &children[i + 1..];
+        let end = rest
+            .iter()
+            .position(|next| {
+                next.cast::<ast::Heading>().is_some_and(|next| next.depth() <= depth)
+            })
+            .unwrap_or(rest.len());
+        let last = rest[..end].iter().rfind(|node| !is_blank(node)).unwrap_or(child);
+        sections.push(child.offset()..last.range().end);
+    }
+    sections
+}
+
+/// Fold runs of items of the same kind in a markup node.
+fn lists(children: &[LinkedNode], folds: &mut Vec<Fold>) {
+    let mut run: Option<(&LinkedNode, &LinkedNode)> = None;
+    for child in children {
+        if is_blank(child) {
+            continue;
+        }
+
+        if let Some((first, last)) = run {
+            if child.kind() == first.kind() {
+                run = Some((first, child));
+                continue;
+            }
+
+            folds.push(Fold {
+                range: first.offset()..last.range().end,
+                kind: FoldKind::List,
+            });
+            run = None;
+        }
+
+        if is_item(child) {
+            run = Some((child, child));
+        }
+    }
+
+    if let Some((first, last)) = run {
+        folds.push(Fold {
+            range: first.offset()..last.range().end,
+            kind: FoldKind::List,
+        });
+    }
+}
+
+/// Whether a node is a list, enumeration, or term list item.
+fn is_item(node: &LinkedNode) -> bool {
+    matches!(
+        node.kind(),
+        SyntaxKind::ListItem | SyntaxKind::EnumItem | SyntaxKind::TermItem
+    )
+}
+
+/// Whether a node is only whitespace.
+pub(crate) fn is_blank(node: &LinkedNode) -> bool {
+    matches!(node.kind(), SyntaxKind::Space | SyntaxKind::Parbreak)
+}
+
+#[cfg(test)]
+mod tests {
+    use typst::syntax::Source;
+
+    use super::*;
+
+    fn test(text: &str) -> Vec<(Range<usize>, FoldKind)> {
+        let source = Source::detached(text);
+        folding_ranges(&source)
+            .into_iter()
+            .map(|fold| (fold.range, fold.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_fold_nested_blocks() {
+        let text = "#{\n  let x = [\n    a\n  ]\n  [b]\n}";
+        assert_eq!(
+            test(text),
+            [(1..text.len(), FoldKind::Block), (13..24, FoldKind::Block)]
+        );
+    }
+
+    #[test]
+    fn test_fold_raw() {
+        assert_eq!(test("```rust\nfn\n```\n`a`"), [(0..14, FoldKind::Raw)]);
+        assert!(test("```a b```").is_empty());
+        assert_eq!(test("```rust\nfn\n``` <code>"), [(0..14, FoldKind::Raw)]);
+    }
+
+    #[test]
+    fn test_fold_headings() {
+        let text = "= A\na\n== B\nb\n\n= C\nc";
+        assert_eq!(
+            test(text),
+            [
+                (0..12, FoldKind::Heading),
+                (6..12, FoldKind::Heading),
+                (14..text.len(), FoldKind::Heading),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fold_lists_and_comments() {
+        assert_eq!(
+            test("- a\n- b\n\n+ c\n/* d\n*/"),
+            [(0..7, FoldKind::List), (13..20, FoldKind::Comment)]
+        );
+    }
+}
@@ -2,12 +2,20 @@
 
 mod analyze;
 mod complete;
+mod fold;
 mod jump;
+mod outline;
+mod selection;
+mod semantic;
 mod tooltip;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::{autocomplete, Completion, CompletionKind};
+pub use self::fold::{folding_ranges, Fold, FoldKind};
 pub use self::jump::{jump_from_click, jump_from_cursor, Jump};
+pub use self::outline::{outline, Symbol, SymbolKind};
+pub use self::selection::{expand_selection, shrink_selection};
+pub use self::semantic::{semantic_token_types, semantic_tokens, SemanticToken};
 pub use self::tooltip::{tooltip, Tooltip};
 
 use std::fmt::Write;
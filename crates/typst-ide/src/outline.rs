@@ -0,0 +1,159 @@
+use std::num::NonZeroUsize;
+
+use ecow::EcoString;
+use typst::syntax::ast::{self, AstNode};
+use typst::syntax::{Source, Span, SyntaxKind, SyntaxNode};
+
+/// An entry in the outline of a source file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Symbol {
+    /// The symbol's name.
+    pub name: EcoString,
+    /// What kind of symbol this is.
+    pub kind: SymbolKind,
+    /// The span of the heading or let binding.
+    pub span: Span,
+    /// The symbols nested in this one.
+    pub children: Vec<Symbol>,
+}
+
+/// A kind of outline entry.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SymbolKind {
+    /// A section heading with its depth.
+    Heading(NonZeroUsize),
+    /// A function defined with a let binding.
+    Function,
+}
+
+/// Build the outline of a source file.
+///
+/// Headings nest by their depth and functions are placed into the section they
+/// are defined in. Definitions within function bodies are not included.
+pub fn outline(source: &Source) -> Vec<Symbol> {
+    let mut flat = vec![];
+    collect(source.root(), &mut flat);
+
+    let mut roots = vec![];
+    let mut stack: Vec<(NonZeroUsize, Symbol)> = vec![];
+    for symbol in flat {
+        if let SymbolKind::Heading(depth) = symbol.kind {
+            while stack.last().is_some_and(|&(prev, _)| prev >= depth) {
+                let (_, done) = stack.pop().unwrap();
+                attach(&mut stack, &mut roots, done);
+            }
+            stack.push((depth, symbol));
+        } else {
+            attach(&mut stack, &mut roots, symbol);
+        }
+    }
+
+    while let Some((_, done)) = stack.pop() {
+        attach(&mut stack, &mut roots, done);
+    }
+
+    roots
+}
+
+/// Add a symbol to the innermost open section or the top level.
+fn attach(stack: &mut [(NonZeroUsize, Symbol)], roots: &mut Vec<Symbol>, symbol: Symbol) {
+    match stack.last_mut() {
+        Some((_, parent)) => parent.children.push(symbol),
+        None => roots.push(symbol),
+    }
+}
+
+/// Collect the symbols in a node and its descendants in source order.
+fn collect(node: &SyntaxNode, flat: &mut Vec<Symbol>) {
+    if let Some(heading) = node.cast::<ast::Heading>() {
+        let name = heading.body().to_untyped().clone().into_text();
+        flat.push(Symbol {
+            name: name.trim().into(),
+            kind: SymbolKind::Heading(heading.depth()),
+            span: node.span(),
+            children: vec![],
+        });
+        return;
+    }
+
+    if let Some(binding) = node.cast::<ast::LetBinding>() {
+        if let Some(ident) = function_name(binding) {
+            flat.push(Symbol {
+                name: ident.get().clone(),
+                kind: SymbolKind::Function,
+                span: node.span(),
+                children: vec![],
+            });
+            return;
+        }
+    }
+
+    if node.kind() == SyntaxKind::Closure {
+        return;
+    }
+
+    for child in node.children() {
+        collect(child, flat);
+    }
+}
+
+/// The name of the function a let binding defines, if any.
+fn function_name(binding: ast::LetBinding) -> Option<ast::Ident> {
+    match binding.kind() {
+        ast::LetBindingKind::Closure(ident) => Some(ident),
+        ast::LetBindingKind::Normal(ast::Pattern::Normal(ast::Expr::Ident(ident))) => {
+            matches!(binding.init(), Some(ast::Expr::Closure(_))).then_some(ident)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use super::*;
+
+    /// Flatten the outline into `(nesting level, name, range)` tuples.
+    fn test(text: &str) -> Vec<(usize, EcoString, Range<usize>)> {
+        fn flatten(
+            source: &Source,
+            symbols: &[Symbol],
+            level: usize,
+            out: &mut Vec<(usize, EcoString, Range<usize>)>,
+        ) {
+            for symbol in symbols {
+                let range = source.range(symbol.span).unwrap();
+                out.push((level, symbol.name.clone(), range));
+                flatten(source, &symbol.children, level + 1, out);
+            }
+        }
+
+        let source = Source::detached(text);
+        let mut out = vec![];
+        flatten(&source, &outline(&source), 0, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_outline_nesting() {
+        assert_eq!(
+            test("= A\n#let f(x) = x\n=== B\n== C\n= D"),
+            [
+                (0, "A".into(), 0..3),
+                (1, "f".into(), 5..17),
+                (1, "B".into(), 18..23),
+                (1, "C".into(), 24..28),
+                (0, "D".into(), 29..32),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outline_top_level_and_closures() {
+        assert_eq!(
+            test("#let g = x => { let h() = 1 }\n== *Bold* title"),
+            [(0, "g".into(), 1..29), (0, "*Bold* title".into(), 30..45)]
+        );
+    }
+}
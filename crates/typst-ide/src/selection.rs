@@ -0,0 +1,184 @@
+use std::ops::Range;
+
+use typst::syntax::{LinkedNode, Source, SyntaxKind};
+
+use crate::fold::{is_blank, sections};
+
+/// Grow a selection to the next enclosing syntactic unit.
+///
+/// The units are, from small to large: the word in a text node, syntax nodes,
+/// paragraphs, and sections from a heading up to the next heading of the same
+/// or a lower depth. Returns the selection unchanged if it already spans the
+/// whole file.
+pub fn expand_selection(source: &Source, selection: Range<usize>) -> Range<usize> {
+    units(source, &selection)
+        .into_iter()
+        .filter(|unit| contains(unit, &selection) && *unit != selection)
+        .min_by_key(|unit| unit.len())
+        .unwrap_or(selection)
+}
+
+/// Shrink a selection to the largest syntactic unit within it that contains
+/// the cursor, undoing [`expand_selection`] on a selection that was grown
+/// from that cursor.
+///
+/// Returns an empty selection at the cursor if there is no smaller unit.
+pub fn shrink_selection(
+    source: &Source,
+    selection: Range<usize>,
+    cursor: usize,
+) -> Range<usize> {
+    let cursor = cursor.clamp(selection.start, selection.end);
+    let cursor = cursor..cursor;
+    units(source, &cursor)
+        .into_iter()
+        .filter(|unit| {
+            contains(&selection, unit) && *unit != selection && !unit.is_empty()
+        })
+        .max_by_key(|unit| unit.len())
+        .unwrap_or(cursor)
+}
+
+/// The syntactic units that contain a selection, from the whole file down to
+/// the innermost node or word.
+fn units(source: &Source, selection: &Range<usize>) -> Vec<Range<usize>> {
+    let mut units = vec![];
+    let mut node = LinkedNode::new(source.root());
+    loop {
+        units.push(node.range());
+        if node.kind() == SyntaxKind::Markup {
+            let children: Vec<_> = node.children().collect();
+            units.extend(paragraphs(&children));
+            units.extend(sections(&children));
+        }
+
+        // An empty selection at a node boundary belongs to the node after it.
+        let child = node
+            .children()
+            .find(|child| {
+                contains(&child.range(), selection)
+                    && (!selection.is_empty() || selection.start < child.range().end)
+            })
+            .or_else(|| {
+                node.children().find(|child| contains(&child.range(), selection))
+            });
+
+        match child {
+            Some(child) => node = child,
+            None => break,
+        }
+    }
+
+    if node.kind() == SyntaxKind::Text {
+        units.push(word(source, node.range(), selection));
+    }
+
+    units
+}
+
+/// The ranges of the paragraphs in a markup node.
+///
+/// Paragraphs are separated by paragraph breaks, headings, and list items.
+fn paragraphs(children: &[LinkedNode]) -> Vec<Range<usize>> {
+    children
+        .split(|child| {
+            matches!(
+                child.kind(),
+                SyntaxKind::Parbreak
+                    | SyntaxKind::Heading
+                    | SyntaxKind::ListItem
+                    | SyntaxKind::EnumItem
+                    | SyntaxKind::TermItem
+            )
+        })
+        .filter_map(|run| {
+            let first = run.iter().find(|child| !is_blank(child))?;
+            let last = run.iter().rfind(|child| !is_blank(child))?;
+            Some(first.offset()..last.range().end)
+        })
+        .collect()
+}
+
+/// The word in a text node around a selection.
+fn word(source: &Source, range: Range<usize>, selection: &Range<usize>) -> Range<usize> {
+    let text = source.text();
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+    let before: usize = text[range.start..selection.start]
+        .chars()
+        .rev()
+        .take_while(is_word)
+        .map(char::len_utf8)
+        .sum();
+    let after: usize = text[selection.end..range.end]
+        .chars()
+        .take_while(is_word)
+        .map(char::len_utf8)
+        .sum();
+    selection.start - before..selection.end + after
+}
+
+/// Whether the outer range fully contains the inner one.
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expand from the cursor until the whole file is selected, then shrink
+    /// back and check that the same units come up in reverse.
+    fn test(text: &str, cursor: usize, expected: &[&str]) {
+        let source = Source::detached(text);
+        let mut selection = cursor..cursor;
+        let mut chain = vec![];
+        loop {
+            let next = expand_selection(&source, selection.clone());
+            if next == selection {
+                break;
+            }
+            chain.push(next.clone());
+            selection = next;
+        }
+
+        let found: Vec<_> = chain.iter().map(|range| &text[range.clone()]).collect();
+        assert_eq!(found, expected);
+
+        for range in chain.iter().rev().skip(1) {
+            selection = shrink_selection(&source, selection, cursor);
+            assert_eq!(selection, *range);
+        }
+
+        selection = shrink_selection(&source, selection, cursor);
+        assert_eq!(selection, cursor..cursor);
+    }
+
+    #[test]
+    fn test_selection_word_to_section() {
+        test(
+            "= A\nSome _emph text_ here.\n\nNext.\n== B\nb\n= C",
+            11,
+            &[
+                "emph",
+                "emph text",
+                "_emph text_",
+                "Some _emph text_ here.",
+                "= A\nSome _emph text_ here.\n\nNext.\n== B\nb",
+                "= A\nSome _emph text_ here.\n\nNext.\n== B\nb\n= C",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_selection_at_node_boundary() {
+        // The cursor belongs to the node after it.
+        test("Hello _world_", 6, &["_", "_world_", "Hello _world_"]);
+        test("Hello _world_", 7, &["world", "_world_", "Hello _world_"]);
+    }
+
+    #[test]
+    fn test_selection_in_trivia() {
+        test("#let x  =  1", 7, &["  ", "let x  =  1", "#let x  =  1"]);
+        test("a  b", 2, &["  ", "a  b"]);
+    }
+}
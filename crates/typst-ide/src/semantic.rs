@@ -0,0 +1,188 @@
+use std::ops::Range;
+
+use typst::syntax::{highlight, is_newline, LinkedNode, Source, SyntaxKind, Tag};
+
+/// A semantic token in the delta-encoded form of the Language Server Protocol.
+///
+/// Tokens never span multiple lines and carry no modifiers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SemanticToken {
+    /// The token's line, relative to the previous token's line.
+    pub delta_line: u32,
+    /// The token's start column in UTF-16 code units, relative to the previous
+    /// token's start column if both are on the same line.
+    pub delta_start: u32,
+    /// The token's length in UTF-16 code units.
+    pub length: u32,
+    /// The token's type, as an index into [`semantic_token_types`].
+    pub token_type: u32,
+}
+
+/// The legend of token types used by [`semantic_tokens`].
+///
+/// The token type of a [`Tag`] is found at index `tag as usize`.
+pub fn semantic_token_types() -> impl Iterator<Item = &'static str> {
+    Tag::LIST.iter().map(|&tag| token_type(tag))
+}
+
+/// Produce the semantic tokens of a source file.
+pub fn semantic_tokens(source: &Source) -> Vec<SemanticToken> {
+    let mut encoder = Encoder { source, tokens: vec![], line: 0, column: 0 };
+    encoder.node(&LinkedNode::new(source.root()), None);
+    encoder.tokens
+}
+
+/// Encodes the highlighted leaves of a syntax tree.
+struct Encoder<'a> {
+    source: &'a Source,
+    tokens: Vec<SemanticToken>,
+    line: usize,
+    column: usize,
+}
+
+impl Encoder<'_> {
+    /// Encode a node, whose leaves inherit the tag of their closest
+    /// highlighted ancestor.
+    fn node(&mut self, node: &LinkedNode, inherited: Option<Tag>) {
+        let tag = highlight(node).or(inherited);
+        if node.get().children().len() > 0 {
+            for child in node.children() {
+                self.node(&child, tag);
+            }
+        } else if let Some(tag) = tag {
+            if !matches!(node.kind(), SyntaxKind::Space | SyntaxKind::Parbreak) {
+                self.token(node.range(), tag);
+            }
+        }
+    }
+
+    /// Encode a token, splitting it at line boundaries.
+    fn token(&mut self, range: Range<usize>, tag: Tag) {
+        let Some(first) = self.source.byte_to_line(range.start) else { return };
+        let Some(last) = self.source.byte_to_line(range.end) else { return };
+        for line in first..=last {
+            let Some(bounds) = self.source.line_to_range(line) else { continue };
+            let start = range.start.max(bounds.start);
+            let end = range.end.min(bounds.end);
+            let Some(text) = self.source.get(start..end) else { continue };
+            let text = text.trim_end_matches(is_newline);
+            if text.is_empty() {
+                continue;
+            }
+
+            let column = self.utf16_column(bounds.start, start);
+            let delta_start =
+                if line == self.line { column - self.column } else { column };
+            self.tokens.push(SemanticToken {
+                delta_line: (line - self.line) as u32,
+                delta_start: delta_start as u32,
+                length: text.encode_utf16().count() as u32,
+                token_type: tag as u32,
+            });
+            self.line = line;
+            self.column = column;
+        }
+    }
+
+    /// The UTF-16 column of a byte offset relative to the start of its line.
+    fn utf16_column(&self, line_start: usize, offset: usize) -> usize {
+        let utf16 = |idx| self.source.byte_to_utf16(idx).unwrap_or(0);
+        utf16(offset) - utf16(line_start)
+    }
+}
+
+/// The name of a tag's token type.
+///
+/// Uses the predefined token types of the protocol where one fits.
+fn token_type(tag: Tag) -> &'static str {
+    match tag {
+        Tag::Comment => "comment",
+        Tag::Punctuation => "punctuation",
+        Tag::Escape => "escape",
+        Tag::Strong => "strong",
+        Tag::Emph => "emph",
+        Tag::Strike => "strike",
+        Tag::Link => "link",
+        Tag::Raw => "raw",
+        Tag::Label => "label",
+        Tag::Ref => "ref",
+        Tag::Heading => "heading",
+        Tag::ListMarker => "marker",
+        Tag::ListTerm => "term",
+        Tag::MathDelimiter => "math-delimiter",
+        Tag::MathOperator => "math-operator",
+        Tag::Keyword => "keyword",
+        Tag::Operator => "operator",
+        Tag::Number => "number",
+        Tag::String => "string",
+        Tag::Function => "function",
+        Tag::Interpolated => "interpolated",
+        Tag::Error => "error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typst::syntax::Source;
+
+    use super::*;
+
+    /// Decode the tokens into absolute `(line, column, length, type)` tuples.
+    fn test(text: &str) -> Vec<(u32, u32, u32, &'static str)> {
+        let source = Source::detached(text);
+        let types: Vec<_> = semantic_token_types().collect();
+        let (mut line, mut column) = (0, 0);
+        semantic_tokens(&source)
+            .into_iter()
+            .map(|token| {
+                if token.delta_line > 0 {
+                    column = 0;
+                }
+                line += token.delta_line;
+                column += token.delta_start;
+                (line, column, token.length, types[token.token_type as usize])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_semantic_tokens_code() {
+        assert_eq!(
+            test("#let x = 1"),
+            [
+                (0, 0, 1, "keyword"),
+                (0, 1, 3, "keyword"),
+                (0, 7, 1, "operator"),
+                (0, 9, 1, "number")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_semantic_tokens_markup() {
+        assert_eq!(
+            test("*a* // c\n= B"),
+            [
+                (0, 0, 1, "strong"),
+                (0, 1, 1, "strong"),
+                (0, 2, 1, "strong"),
+                (0, 4, 4, "comment"),
+                (1, 0, 1, "heading"),
+                (1, 2, 1, "heading"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_semantic_tokens_split_lines() {
+        assert_eq!(test("/* a\nbc */"), [(0, 0, 4, "comment"), (1, 0, 5, "comment")]);
+    }
+
+    #[test]
+    fn test_semantic_tokens_utf16() {
+        assert_eq!(
+            test("ü😀 _x_"),
+            [(0, 4, 1, "emph"), (0, 5, 1, "emph"), (0, 6, 1, "emph")]
+        );
+    }
+}
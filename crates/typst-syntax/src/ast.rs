@@ -1,14 +1,17 @@
 //! A typed layer over the untyped syntax tree.
 //!
-//! The AST is rooted in the [`Markup`] node.
+//! The AST is rooted in the [`Markup`] node. The accessors never panic, not
+//! even on trees that were not produced by the parser: Missing or malformed
+//! children yield defaults instead.
 
-use std::num::NonZeroUsize;
-use std::ops::Deref;
+use std::num::{NonZeroUsize, ParseIntError};
+use std::ops::{Deref, RangeInclusive};
 
 use ecow::EcoString;
 use unscanny::Scanner;
 
-use crate::{is_newline, Span, SyntaxKind, SyntaxNode};
+use crate::lexer::{is_unicode_escape, unicode_escape};
+use crate::{is_newline, LinkedNode, Span, SyntaxKind, SyntaxNode};
 
 /// A typed AST node.
 pub trait AstNode<'a>: Sized {
@@ -24,6 +27,20 @@ pub trait AstNode<'a>: Sized {
     }
 }
 
+/// A typed node together with the trivia around it.
+///
+/// This struct is created by
+/// [`LinkedNode::cast_with_trivia`](crate::LinkedNode::cast_with_trivia).
+#[derive(Debug, Clone)]
+pub struct WithTrivia<'a, T> {
+    /// The typed node.
+    pub node: T,
+    /// The spaces and comments directly before the node.
+    pub leading: Vec<LinkedNode<'a>>,
+    /// The spaces and comments after the node on the same line.
+    pub trailing: Vec<LinkedNode<'a>>,
+}
+
 /// A static syntax node used as a fallback value. This is returned instead of
 /// panicking when the syntactical structure isn't valid. In a normal
 /// compilation, evaluation isn't attempted on a broken file, but for IDE
@@ -96,17 +113,23 @@ pub enum Expr<'a> {
     Linebreak(Linebreak<'a>),
     /// A paragraph break, indicated by one or multiple blank lines.
     Parbreak(Parbreak<'a>),
-    /// An escape sequence: `\#`, `\u{1F5FA}`.
+    /// A horizontal rule, three or more dashes on their own line: `---`.
+    Rule(Rule<'a>),
+    /// An escape sequence: `\#`, `\u{1F5FA}`, `\u{name:EM DASH}`.
     Escape(Escape<'a>),
     /// A shorthand for a unicode codepoint. For example, `~` for non-breaking
     /// space or `-?` for a soft hyphen.
     Shorthand(Shorthand<'a>),
+    /// An emoji shortcode: `:face.grin:`.
+    Shortcode(Shortcode<'a>),
     /// A smart quote: `'` or `"`.
     SmartQuote(SmartQuote<'a>),
     /// Strong content: `*Strong*`.
     Strong(Strong<'a>),
     /// Emphasized content: `_Emphasized_`.
     Emph(Emph<'a>),
+    /// Struck-through content: `~~Struck~~`.
+    Strike(Strike<'a>),
     /// Raw text with optional syntax highlighting: `` `...` ``.
     Raw(Raw<'a>),
     /// A hyperlink: `https://typst.org`.
@@ -115,6 +138,8 @@ pub enum Expr<'a> {
     Label(Label<'a>),
     /// A reference: `@target`, `@target[..]`.
     Ref(Ref<'a>),
+    /// A footnote: `^[..]`.
+    Footnote(Footnote<'a>),
     /// A section heading: `= Introduction`.
     Heading(Heading<'a>),
     /// An item in a bullet list: `- ...`.
@@ -123,6 +148,8 @@ pub enum Expr<'a> {
     Enum(EnumItem<'a>),
     /// An item in a term list: `/ Term: Details`.
     Term(TermItem<'a>),
+    /// A table in markup: `| a | b |`.
+    Table(Table<'a>),
     /// A mathematical equation: `$x$`, `$ x^2 $`.
     Equation(Equation<'a>),
     /// The contents of a mathematical equation: `x^2 + 1`.
@@ -155,8 +182,10 @@ pub enum Expr<'a> {
     Float(Float<'a>),
     /// A numeric value with a unit: `12pt`, `3cm`, `2em`, `90deg`, `50%`.
     Numeric(Numeric<'a>),
-    /// A quoted string: `"..."`.
+    /// A quoted string: `"..."` or `r"..."`.
     Str(Str<'a>),
+    /// A string with embedded code: `f"Hello {name}!"`.
+    Interpolation(Interpolation<'a>),
     /// A code block: `{ let x = 1; x + 2 }`.
     Code(CodeBlock<'a>),
     /// A content block: `[*Hi* there!]`.
@@ -218,21 +247,26 @@ impl<'a> AstNode<'a> for Expr<'a> {
     fn from_untyped(node: &'a SyntaxNode) -> Option<Self> {
         match node.kind() {
             SyntaxKind::Linebreak => node.cast().map(Self::Linebreak),
+            SyntaxKind::Rule => node.cast().map(Self::Rule),
             SyntaxKind::Parbreak => node.cast().map(Self::Parbreak),
             SyntaxKind::Text => node.cast().map(Self::Text),
             SyntaxKind::Escape => node.cast().map(Self::Escape),
             SyntaxKind::Shorthand => node.cast().map(Self::Shorthand),
+            SyntaxKind::Shortcode => node.cast().map(Self::Shortcode),
             SyntaxKind::SmartQuote => node.cast().map(Self::SmartQuote),
             SyntaxKind::Strong => node.cast().map(Self::Strong),
             SyntaxKind::Emph => node.cast().map(Self::Emph),
+            SyntaxKind::Strike => node.cast().map(Self::Strike),
             SyntaxKind::Raw => node.cast().map(Self::Raw),
             SyntaxKind::Link => node.cast().map(Self::Link),
             SyntaxKind::Label => node.cast().map(Self::Label),
             SyntaxKind::Ref => node.cast().map(Self::Ref),
+            SyntaxKind::Footnote => node.cast().map(Self::Footnote),
             SyntaxKind::Heading => node.cast().map(Self::Heading),
             SyntaxKind::ListItem => node.cast().map(Self::List),
             SyntaxKind::EnumItem => node.cast().map(Self::Enum),
             SyntaxKind::TermItem => node.cast().map(Self::Term),
+            SyntaxKind::Table => node.cast().map(Self::Table),
             SyntaxKind::Equation => node.cast().map(Self::Equation),
             SyntaxKind::Math => node.cast().map(Self::Math),
             SyntaxKind::MathIdent => node.cast().map(Self::MathIdent),
@@ -250,6 +284,7 @@ impl<'a> AstNode<'a> for Expr<'a> {
             SyntaxKind::Float => node.cast().map(Self::Float),
             SyntaxKind::Numeric => node.cast().map(Self::Numeric),
             SyntaxKind::Str => node.cast().map(Self::Str),
+            SyntaxKind::Interpolation => node.cast().map(Self::Interpolation),
             SyntaxKind::CodeBlock => node.cast().map(Self::Code),
             SyntaxKind::ContentBlock => node.cast().map(Self::Content),
             SyntaxKind::Parenthesized => node.cast().map(Self::Parenthesized),
@@ -282,20 +317,25 @@ impl<'a> AstNode<'a> for Expr<'a> {
             Self::Text(v) => v.to_untyped(),
             Self::Space(v) => v.to_untyped(),
             Self::Linebreak(v) => v.to_untyped(),
+            Self::Rule(v) => v.to_untyped(),
             Self::Parbreak(v) => v.to_untyped(),
             Self::Escape(v) => v.to_untyped(),
             Self::Shorthand(v) => v.to_untyped(),
+            Self::Shortcode(v) => v.to_untyped(),
             Self::SmartQuote(v) => v.to_untyped(),
             Self::Strong(v) => v.to_untyped(),
             Self::Emph(v) => v.to_untyped(),
+            Self::Strike(v) => v.to_untyped(),
             Self::Raw(v) => v.to_untyped(),
             Self::Link(v) => v.to_untyped(),
             Self::Label(v) => v.to_untyped(),
             Self::Ref(v) => v.to_untyped(),
+            Self::Footnote(v) => v.to_untyped(),
             Self::Heading(v) => v.to_untyped(),
             Self::List(v) => v.to_untyped(),
             Self::Enum(v) => v.to_untyped(),
             Self::Term(v) => v.to_untyped(),
+            Self::Table(v) => v.to_untyped(),
             Self::Equation(v) => v.to_untyped(),
             Self::Math(v) => v.to_untyped(),
             Self::MathIdent(v) => v.to_untyped(),
@@ -313,6 +353,7 @@ impl<'a> AstNode<'a> for Expr<'a> {
             Self::Float(v) => v.to_untyped(),
             Self::Numeric(v) => v.to_untyped(),
             Self::Str(v) => v.to_untyped(),
+            Self::Interpolation(v) => v.to_untyped(),
             Self::Code(v) => v.to_untyped(),
             Self::Content(v) => v.to_untyped(),
             Self::Array(v) => v.to_untyped(),
@@ -353,6 +394,7 @@ impl Expr<'_> {
                 | Self::Float(_)
                 | Self::Numeric(_)
                 | Self::Str(_)
+                | Self::Interpolation(_)
                 | Self::Code(_)
                 | Self::Content(_)
                 | Self::Array(_)
@@ -425,7 +467,12 @@ node! {
 }
 
 node! {
-    /// An escape sequence: `\#`, `\u{1F5FA}`.
+    /// A horizontal rule, three or more dashes on their own line: `---`.
+    Rule
+}
+
+node! {
+    /// An escape sequence: `\#`, `\u{1F5FA}`, `\u{name:EM DASH}`.
     Escape
 }
 
@@ -433,13 +480,9 @@ impl Escape<'_> {
     /// Get the escaped character.
     pub fn get(self) -> char {
         let mut s = Scanner::new(self.0.text());
-        s.expect('\\');
+        s.eat_if('\\');
         if s.eat_if("u{") {
-            let hex = s.eat_while(char::is_ascii_hexdigit);
-            u32::from_str_radix(hex, 16)
-                .ok()
-                .and_then(std::char::from_u32)
-                .unwrap_or_default()
+            unicode_escape(s.eat_while(is_unicode_escape)).unwrap_or_default()
         } else {
             s.eat().unwrap_or_default()
         }
@@ -514,6 +557,18 @@ impl Shorthand<'_> {
     }
 }
 
+node! {
+    /// An emoji shortcode: `:face.grin:`.
+    Shortcode
+}
+
+impl<'a> Shortcode<'a> {
+    /// Get the name of the emoji, without the surrounding colons.
+    pub fn get(self) -> &'a str {
+        self.0.text().trim_matches(':')
+    }
+}
+
 node! {
     /// A smart quote: `'` or `"`.
     SmartQuote
@@ -550,6 +605,28 @@ impl<'a> Emph<'a> {
     }
 }
 
+node! {
+    /// Struck-through content: `~~Struck~~`.
+    Strike
+}
+
+impl<'a> Strike<'a> {
+    /// The contents of the strike node.
+    pub fn body(self) -> Markup<'a> {
+        self.0.cast_first_match().unwrap_or_default()
+    }
+
+    /// Whether the closing delimiter is present. If it is missing, the opening
+    /// tildes are two non-breaking spaces instead.
+    pub fn closed(self) -> bool {
+        self.0
+            .children()
+            .filter(|node| node.kind() == SyntaxKind::StrikeDelim)
+            .count()
+            == 2
+    }
+}
+
 node! {
     /// Raw text with optional syntax highlighting: `` `...` ``.
     Raw
@@ -572,6 +649,12 @@ impl<'a> Raw<'a> {
         self.0.cast_first_match()
     }
 
+    /// Options following the language tag, like a range of lines to display
+    /// or lines to highlight.
+    pub fn meta(self) -> Option<RawMeta<'a>> {
+        self.0.cast_first_match()
+    }
+
     /// Whether the raw text should be displayed in a separate block.
     pub fn block(self) -> bool {
         self.0
@@ -581,6 +664,11 @@ impl<'a> Raw<'a> {
                 e.kind() == SyntaxKind::RawTrimmed && e.text().chars().any(is_newline)
             })
     }
+
+    /// The label attached to the end of the raw text: `` `x` <code> ``.
+    pub fn label(self) -> Option<Label<'a>> {
+        self.0.cast_last_match()
+    }
 }
 
 node! {
@@ -595,6 +683,39 @@ impl<'a> RawLang<'a> {
     }
 }
 
+node! {
+    /// Options following the language tag of raw text: ``rust:3..10{5} ``.
+    RawMeta
+}
+
+impl<'a> RawMeta<'a> {
+    /// The range of lines to display, inclusive on both ends.
+    ///
+    /// Fails if a line number doesn't fit into a `usize`.
+    pub fn line_range(self) -> Option<Result<RangeInclusive<usize>, ParseIntError>> {
+        let text = self.0.text().strip_prefix(':')?;
+        let range = text.split('{').next()?;
+        Some(parse_line_range(range))
+    }
+
+    /// The ranges of lines to highlight, inclusive on both ends.
+    ///
+    /// Fails if a line number doesn't fit into a `usize`.
+    pub fn highlight_lines(self) -> Result<Vec<RangeInclusive<usize>>, ParseIntError> {
+        let Some((_, list)) = self.0.text().split_once('{') else {
+            return Ok(vec![]);
+        };
+
+        list.trim_end_matches('}').split(',').map(parse_line_range).collect()
+    }
+}
+
+/// Parses a line number or an inclusive range of line numbers: `3` or `3..10`.
+fn parse_line_range(text: &str) -> Result<RangeInclusive<usize>, ParseIntError> {
+    let (start, end) = text.split_once("..").unwrap_or((text, text));
+    Ok(start.parse()?..=end.parse()?)
+}
+
 node! {
     /// A raw delimiter in single or 3+ backticks: `` ` ``.
     RawDelim
@@ -645,6 +766,18 @@ impl<'a> Ref<'a> {
     }
 }
 
+node! {
+    /// A footnote: `^[..]`.
+    Footnote
+}
+
+impl<'a> Footnote<'a> {
+    /// The contents of the footnote.
+    pub fn body(self) -> Markup<'a> {
+        self.0.cast_first_match::<ContentBlock>().unwrap_or_default().body()
+    }
+}
+
 node! {
     /// A section heading: `= Introduction`.
     Heading
@@ -664,6 +797,11 @@ impl<'a> Heading<'a> {
             .and_then(|node| node.len().try_into().ok())
             .unwrap_or(NonZeroUsize::new(1).unwrap())
     }
+
+    /// The label attached to the end of the heading: `= Intro <intro>`.
+    pub fn label(self) -> Option<Label<'a>> {
+        self.0.cast_last_match()
+    }
 }
 
 node! {
@@ -672,6 +810,14 @@ node! {
 }
 
 impl<'a> ListItem<'a> {
+    /// Whether the item's checkbox is checked, if it has one.
+    pub fn checked(self) -> Option<bool> {
+        self.0
+            .children()
+            .find(|node| node.kind() == SyntaxKind::ListCheckbox)
+            .map(|node| node.text() != "[ ]")
+    }
+
     /// The contents of the list item.
     pub fn body(self) -> Markup<'a> {
         self.0.cast_first_match().unwrap_or_default()
@@ -715,6 +861,85 @@ impl<'a> TermItem<'a> {
     }
 }
 
+node! {
+    /// A table in markup: a header row, a separator line like `|---|---|`, and
+    /// body rows.
+    Table
+}
+
+impl<'a> Table<'a> {
+    /// The rows of the table, starting with the header row.
+    pub fn rows(self) -> impl DoubleEndedIterator<Item = TableRow<'a>> {
+        self.0.children().filter_map(SyntaxNode::cast)
+    }
+
+    /// The separator between the header row and the body rows.
+    pub fn separator(self) -> Option<TableSeparator<'a>> {
+        self.0.cast_first_match()
+    }
+}
+
+node! {
+    /// Separates the header of a markup table from its body: `|---|:-:|`.
+    TableSeparator
+}
+
+impl<'a> TableSeparator<'a> {
+    /// The alignment each column specifies with colons: `:--` for left, `:-:`
+    /// for center, and `--:` for right.
+    pub fn alignments(self) -> Vec<Option<TableAlignment>> {
+        let text = self.0.text().trim();
+        let text = text.strip_prefix('|').unwrap_or(text);
+        let text = text.strip_suffix('|').unwrap_or(text);
+        text.split('|')
+            .map(|cell| {
+                let cell = cell.trim();
+                match (cell.starts_with(':'), cell.ends_with(':')) {
+                    (true, true) => Some(TableAlignment::Center),
+                    (true, false) => Some(TableAlignment::Left),
+                    (false, true) => Some(TableAlignment::Right),
+                    (false, false) => Option::None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The alignment of a column in a markup table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TableAlignment {
+    /// Align the column's cells to the left: `:--`.
+    Left,
+    /// Center the column's cells: `:-:`.
+    Center,
+    /// Align the column's cells to the right: `--:`.
+    Right,
+}
+
+node! {
+    /// A row in a markup table: `| a | b |`.
+    TableRow
+}
+
+impl<'a> TableRow<'a> {
+    /// The cells of the row.
+    pub fn cells(self) -> impl DoubleEndedIterator<Item = TableCell<'a>> {
+        self.0.children().filter_map(SyntaxNode::cast)
+    }
+}
+
+node! {
+    /// A cell in a markup table row: `a`.
+    TableCell
+}
+
+impl<'a> TableCell<'a> {
+    /// The contents of the cell.
+    pub fn body(self) -> Markup<'a> {
+        self.0.cast_first_match().unwrap_or_default()
+    }
+}
+
 node! {
     /// A mathemathical equation: `$x$`, `$ x^2 $`.
     Equation
@@ -731,7 +956,17 @@ impl<'a> Equation<'a> {
         let is_space = |node: Option<&SyntaxNode>| {
             node.map(SyntaxNode::kind) == Some(SyntaxKind::Space)
         };
-        is_space(self.0.children().nth(1)) && is_space(self.0.children().nth_back(1))
+        let mut inner = self
+            .0
+            .children()
+            .rev()
+            .skip_while(|node| node.kind() != SyntaxKind::Dollar);
+        is_space(self.0.children().nth(1)) && is_space(inner.nth(1))
+    }
+
+    /// The label attached to the end of the equation: `$ x $ <eq>`.
+    pub fn label(self) -> Option<Label<'a>> {
+        self.0.cast_last_match()
     }
 }
 
@@ -1033,53 +1268,120 @@ pub enum Unit {
 }
 
 node! {
-    /// A quoted string: `"..."`.
+    /// A quoted string: `"..."` or `r"..."`.
     Str
 }
 
 impl Str<'_> {
     /// Get the string value with resolved escape sequences.
+    ///
+    /// Raw strings (`r"..."`) are returned as is since they have no escapes.
     pub fn get(self) -> EcoString {
-        let text = self.0.text();
-        let unquoted = &text[1..text.len() - 1];
-        if !unquoted.contains('\\') {
-            return unquoted.into();
+        let mut text = self.0.text().as_str();
+        let raw = text.starts_with('r');
+        if raw {
+            text = &text[1..];
+        }
+        let text = text.strip_prefix('"').unwrap_or(text);
+        let text = text.strip_suffix('"').unwrap_or(text);
+        if raw {
+            return text.into();
         }
 
-        let mut out = EcoString::with_capacity(unquoted.len());
-        let mut s = Scanner::new(unquoted);
+        unescape(text, false)
+    }
+}
 
-        while let Some(c) = s.eat() {
-            if c != '\\' {
-                out.push(c);
-                continue;
-            }
+node! {
+    /// A string with embedded code: `f"Hello {name}!"`.
+    Interpolation
+}
 
-            let start = s.locate(-1);
-            match s.eat() {
-                Some('\\') => out.push('\\'),
-                Some('"') => out.push('"'),
-                Some('n') => out.push('\n'),
-                Some('r') => out.push('\r'),
-                Some('t') => out.push('\t'),
-                Some('u') if s.eat_if('{') => {
-                    let sequence = s.eat_while(char::is_ascii_hexdigit);
-                    s.eat_if('}');
-
-                    match u32::from_str_radix(sequence, 16)
-                        .ok()
-                        .and_then(std::char::from_u32)
-                    {
-                        Some(c) => out.push(c),
-                        Option::None => out.push_str(s.from(start)),
-                    }
+impl<'a> Interpolation<'a> {
+    /// The literal pieces and embedded code blocks, in order.
+    pub fn parts(self) -> impl DoubleEndedIterator<Item = InterpolationPart<'a>> {
+        self.0.children().filter_map(SyntaxNode::cast)
+    }
+}
+
+/// A piece of an interpolated string.
+#[derive(Debug, Copy, Clone, Hash)]
+pub enum InterpolationPart<'a> {
+    /// Literal text: `Hello `.
+    Str(StrPart<'a>),
+    /// An embedded code block: `{name}`.
+    Code(CodeBlock<'a>),
+}
+
+impl<'a> AstNode<'a> for InterpolationPart<'a> {
+    fn from_untyped(node: &'a SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::StrPart => node.cast().map(Self::Str),
+            SyntaxKind::CodeBlock => node.cast().map(Self::Code),
+            _ => Option::None,
+        }
+    }
+
+    fn to_untyped(self) -> &'a SyntaxNode {
+        match self {
+            Self::Str(v) => v.to_untyped(),
+            Self::Code(v) => v.to_untyped(),
+        }
+    }
+}
+
+node! {
+    /// A literal piece of an interpolated string.
+    StrPart
+}
+
+impl StrPart<'_> {
+    /// Get the text with resolved escape sequences, including escaped braces.
+    pub fn get(self) -> EcoString {
+        unescape(self.0.text(), true)
+    }
+}
+
+/// Resolve the escape sequences in the contents of a string.
+///
+/// Braces can only be escaped in interpolated strings. Elsewhere, `\{` stays
+/// as is, like any other unknown escape sequence.
+fn unescape(text: &str, braces: bool) -> EcoString {
+    if !text.contains('\\') {
+        return text.into();
+    }
+
+    let mut out = EcoString::with_capacity(text.len());
+    let mut s = Scanner::new(text);
+
+    while let Some(c) = s.eat() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        let start = s.locate(-1);
+        match s.eat() {
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some(c @ ('{' | '}')) if braces => out.push(c),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('u') if s.eat_if('{') => {
+                let sequence = s.eat_while(is_unicode_escape);
+                s.eat_if('}');
+
+                match unicode_escape(sequence) {
+                    Some(c) => out.push(c),
+                    Option::None => out.push_str(s.from(start)),
                 }
-                _ => out.push_str(s.from(start)),
             }
+            _ => out.push_str(s.from(start)),
         }
-
-        out
     }
+
+    out
 }
 
 node! {
@@ -2135,3 +2437,409 @@ impl<'a> FuncReturn<'a> {
         self.0.cast_last_match()
     }
 }
+
+/// Traverses the typed syntax tree.
+///
+/// Every method defaults to walking into the node's children, so implementors
+/// only override the nodes they are interested in and call the matching
+/// `walk_*` function wherever the traversal should continue.
+pub trait Visitor<'a> {
+    /// Visit a markup node.
+    fn visit_markup(&mut self, markup: Markup<'a>) {
+        walk_markup(self, markup);
+    }
+
+    /// Visit a code node.
+    fn visit_code(&mut self, code: Code<'a>) {
+        walk_code(self, code);
+    }
+
+    /// Visit an expression.
+    fn visit_expr(&mut self, expr: Expr<'a>) {
+        walk_expr(self, expr);
+    }
+}
+
+/// Visit the expressions in markup.
+pub fn walk_markup<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, markup: Markup<'a>) {
+    for expr in markup.exprs() {
+        visitor.visit_expr(expr);
+    }
+}
+
+/// Visit the expressions in code.
+pub fn walk_code<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, code: Code<'a>) {
+    for expr in code.exprs() {
+        visitor.visit_expr(expr);
+    }
+}
+
+/// Visit the markup, code and expressions nested in an expression.
+///
+/// Identifiers that name fields, arguments and parameters are visited as
+/// expressions, too.
+pub fn walk_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: Expr<'a>) {
+    match expr {
+        Expr::Math(math) => {
+            for expr in math.exprs() {
+                visitor.visit_expr(expr);
+            }
+        }
+        _ => walk_children(visitor, expr.to_untyped()),
+    }
+}
+
+/// Visit the typed nodes below an untyped node.
+fn walk_children<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, node: &'a SyntaxNode) {
+    for child in node.children() {
+        if child.kind().is_trivia() {
+            continue;
+        }
+
+        if let Some(markup) = child.cast() {
+            visitor.visit_markup(markup);
+        } else if let Some(code) = child.cast() {
+            visitor.visit_code(code);
+        } else if let Some(expr) = child.cast() {
+            visitor.visit_expr(expr);
+        } else {
+            walk_children(visitor, child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_visitor() {
+        struct Headings(Vec<NonZeroUsize>);
+
+        impl<'a> Visitor<'a> for Headings {
+            fn visit_expr(&mut self, expr: Expr<'a>) {
+                if let Expr::Heading(heading) = expr {
+                    self.0.push(heading.depth());
+                }
+                walk_expr(self, expr);
+            }
+        }
+
+        let root = parse("= A\n#[== B]\n#{ [=== C] }\n#f[= D]");
+        let mut headings = Headings(vec![]);
+        headings.visit_markup(root.cast().unwrap());
+        let depths: Vec<_> = headings.0.into_iter().map(NonZeroUsize::get).collect();
+        assert_eq!(depths, [1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_accessors_never_panic() {
+        use crate::SyntaxKind as K;
+
+        /// Calls the accessors of an expression and its descendants.
+        struct Access;
+
+        impl<'a> Visitor<'a> for Access {
+            fn visit_expr(&mut self, expr: Expr<'a>) {
+                let _ = (expr.hash(), expr.is_literal());
+                match expr {
+                    Expr::Text(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Escape(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Shorthand(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Shortcode(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::SmartQuote(v) => {
+                        let _ = v.double();
+                    }
+                    Expr::Strike(v) => {
+                        let _ = v.closed();
+                    }
+                    Expr::Raw(v) => {
+                        v.lines().for_each(|line| {
+                            let _ = line.get();
+                        });
+                        let _ = (v.lang().map(RawLang::get), v.block(), v.label());
+                        if let Some(meta) = v.meta() {
+                            let _ = (meta.line_range(), meta.highlight_lines());
+                        }
+                    }
+                    Expr::Link(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Label(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Ref(v) => {
+                        let _ = v.target();
+                    }
+                    Expr::Heading(v) => {
+                        let _ = (v.depth(), v.label().map(Label::get));
+                    }
+                    Expr::List(v) => {
+                        let _ = v.checked();
+                    }
+                    Expr::Enum(v) => {
+                        let _ = v.number();
+                    }
+                    Expr::Table(v) => {
+                        let _ = v.separator().map(TableSeparator::alignments);
+                    }
+                    Expr::Equation(v) => {
+                        let _ = (v.block(), v.label());
+                    }
+                    Expr::MathIdent(v) => {
+                        let _ = v.as_str();
+                    }
+                    Expr::MathAttach(v) => {
+                        let _ = v.primes().map(MathPrimes::count);
+                    }
+                    Expr::MathPrimes(v) => {
+                        let _ = v.count();
+                    }
+                    Expr::MathRoot(v) => {
+                        let _ = v.index();
+                    }
+                    Expr::Ident(v) => {
+                        let _ = v.as_str();
+                    }
+                    Expr::Bool(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Int(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Float(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Numeric(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Str(v) => {
+                        let _ = v.get();
+                    }
+                    Expr::Interpolation(v) => {
+                        for part in v.parts() {
+                            if let InterpolationPart::Str(part) = part {
+                                let _ = part.get();
+                            }
+                        }
+                    }
+                    Expr::Unary(v) => {
+                        let _ = v.op();
+                    }
+                    Expr::Binary(v) => {
+                        let _ = v.op();
+                    }
+                    Expr::FuncCall(v) => {
+                        let _ = v.args().trailing_comma();
+                    }
+                    Expr::Closure(v) => {
+                        for param in v.params().children() {
+                            if let Param::Pos(pattern) = param {
+                                let _ = pattern.bindings();
+                            }
+                        }
+                    }
+                    Expr::Let(v) => {
+                        let _ = v.kind().bindings();
+                    }
+                    Expr::DestructAssign(v) => {
+                        let _ = v.pattern().bindings();
+                    }
+                    Expr::For(v) => {
+                        let _ = v.pattern().bindings();
+                    }
+                    Expr::Import(v) => {
+                        if let Some(Imports::Items(items)) = v.imports() {
+                            for item in items.iter() {
+                                let _ = (item.original_name(), item.bound_name());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                walk_expr(self, expr);
+            }
+        }
+
+        // Trees that were not produced by the parser, e.g. because they were
+        // deserialized or rewritten, can contain anything.
+        let kinds = [
+            K::Markup,
+            K::Text,
+            K::Space,
+            K::Linebreak,
+            K::Parbreak,
+            K::Rule,
+            K::Escape,
+            K::Shorthand,
+            K::Shortcode,
+            K::SmartQuote,
+            K::Strong,
+            K::Emph,
+            K::Strike,
+            K::Raw,
+            K::RawLang,
+            K::RawMeta,
+            K::RawDelim,
+            K::RawTrimmed,
+            K::Link,
+            K::Label,
+            K::Ref,
+            K::RefMarker,
+            K::Footnote,
+            K::FootnoteMarker,
+            K::Heading,
+            K::HeadingMarker,
+            K::ListItem,
+            K::ListMarker,
+            K::ListCheckbox,
+            K::EnumItem,
+            K::EnumMarker,
+            K::TermItem,
+            K::TermMarker,
+            K::Table,
+            K::TableRow,
+            K::TableCell,
+            K::TableDelim,
+            K::TableSeparator,
+            K::Equation,
+            K::Math,
+            K::MathIdent,
+            K::MathAlignPoint,
+            K::MathDelimited,
+            K::MathAttach,
+            K::MathPrimes,
+            K::MathFrac,
+            K::MathRoot,
+            K::Hash,
+            K::LeftBrace,
+            K::RightBrace,
+            K::LeftBracket,
+            K::RightBracket,
+            K::LeftParen,
+            K::RightParen,
+            K::Comma,
+            K::Semicolon,
+            K::Colon,
+            K::Star,
+            K::Underscore,
+            K::StrikeDelim,
+            K::Dollar,
+            K::Plus,
+            K::Minus,
+            K::Slash,
+            K::Hat,
+            K::Prime,
+            K::Dot,
+            K::Eq,
+            K::EqEq,
+            K::ExclEq,
+            K::Lt,
+            K::LtEq,
+            K::Gt,
+            K::GtEq,
+            K::PlusEq,
+            K::HyphEq,
+            K::StarEq,
+            K::SlashEq,
+            K::Dots,
+            K::Arrow,
+            K::Root,
+            K::Not,
+            K::And,
+            K::Or,
+            K::None,
+            K::Auto,
+            K::Let,
+            K::Set,
+            K::Show,
+            K::Context,
+            K::If,
+            K::Else,
+            K::For,
+            K::In,
+            K::While,
+            K::Break,
+            K::Continue,
+            K::Return,
+            K::Import,
+            K::Include,
+            K::As,
+            K::Code,
+            K::Ident,
+            K::Bool,
+            K::Int,
+            K::Float,
+            K::Numeric,
+            K::Str,
+            K::StrDelim,
+            K::StrPart,
+            K::Interpolation,
+            K::CodeBlock,
+            K::ContentBlock,
+            K::Parenthesized,
+            K::Array,
+            K::Dict,
+            K::Named,
+            K::Keyed,
+            K::Unary,
+            K::Binary,
+            K::FieldAccess,
+            K::FuncCall,
+            K::Args,
+            K::Spread,
+            K::Closure,
+            K::Params,
+            K::LetBinding,
+            K::SetRule,
+            K::ShowRule,
+            K::Contextual,
+            K::Conditional,
+            K::WhileLoop,
+            K::ForLoop,
+            K::ModuleImport,
+            K::ImportItems,
+            K::RenamedImportItem,
+            K::ModuleInclude,
+            K::LoopBreak,
+            K::LoopContinue,
+            K::FuncReturn,
+            K::Destructuring,
+            K::DestructAssignment,
+            K::LineComment,
+            K::BlockComment,
+        ];
+        let texts =
+            ["", "\"", "r", "r\"", "\\", "\\u{", "é", "`", "<", "@", "0x", ":1..{"];
+        for &kind in &kinds {
+            for text in texts {
+                let children = kinds.iter().map(|&k| SyntaxNode::leaf(k, text)).collect();
+                for node in [
+                    SyntaxNode::leaf(kind, text),
+                    SyntaxNode::inner(kind, vec![]),
+                    SyntaxNode::inner(kind, children),
+                ] {
+                    let markup = SyntaxNode::inner(K::Markup, vec![node.clone()]);
+                    Access.visit_markup(markup.cast().unwrap());
+                    let code = SyntaxNode::inner(K::Code, vec![node]);
+                    Access.visit_code(code.cast().unwrap());
+                }
+            }
+        }
+
+        // Error-recovered trees.
+        for text in ["= ", "#let", "#for in", "#import \"a\": ", "$a^$", "#f(..", "#(a:)"]
+        {
+            Access.visit_markup(parse(text).cast().unwrap());
+        }
+    }
+}
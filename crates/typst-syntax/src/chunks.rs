@@ -0,0 +1,161 @@
+use std::io::{self, BufRead};
+
+use crate::{parse, LinkedNode, SyntaxKind, SyntaxNode};
+
+/// Parses markup from a reader in chunks, without holding the whole text or
+/// tree in memory.
+///
+/// The text is split at paragraph breaks between top-level markup, so that
+/// each chunk parses exactly like it would as part of the whole text. The
+/// children of all chunks together are thus the children of [`parse`]'s root
+/// node. Only a chunk and the line after it are kept in memory at any time.
+///
+/// As each chunk is parsed on its own, its spans are
+/// [detached](crate::Span::detached).
+pub fn parse_chunks<R: BufRead>(reader: R) -> Chunks<R> {
+    Chunks {
+        reader,
+        buf: String::new(),
+        next: String::new(),
+        offset: 0,
+        blank: false,
+        done: false,
+    }
+}
+
+/// An iterator over the chunks of a reader.
+///
+/// This struct is created by [`parse_chunks`].
+#[derive(Debug)]
+pub struct Chunks<R> {
+    reader: R,
+    buf: String,
+    next: String,
+    offset: usize,
+    blank: bool,
+    done: bool,
+}
+
+impl<R: BufRead> Chunks<R> {
+    /// Emit the buffered text as a chunk.
+    fn emit(&mut self, root: SyntaxNode) -> Chunk {
+        let chunk = Chunk { offset: self.offset, root };
+        self.offset += self.buf.len();
+        self.buf.clear();
+        chunk
+    }
+}
+
+impl<R: BufRead> Iterator for Chunks<R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            self.next.clear();
+            if let Err(err) = self.reader.read_line(&mut self.next) {
+                self.done = true;
+                return Some(Err(err));
+            }
+
+            if self.next.is_empty() {
+                self.done = true;
+                if self.buf.is_empty() {
+                    return None;
+                }
+                let root = parse(&self.buf);
+                return Some(Ok(self.emit(root)));
+            }
+
+            // A line that starts at the left margin after a blank line may
+            // start a new chunk, unless the blank line is nested in an
+            // unclosed delimiter or the like.
+            let blank = self.next.trim().is_empty();
+            if self.blank && !self.next.starts_with(char::is_whitespace) {
+                let root = parse(&self.buf);
+                if root.children().last().map(SyntaxNode::kind)
+                    == Some(SyntaxKind::Parbreak)
+                {
+                    let chunk = self.emit(root);
+                    self.buf.push_str(&self.next);
+                    self.blank = blank;
+                    return Some(Ok(chunk));
+                }
+            }
+
+            self.buf.push_str(&self.next);
+            self.blank = blank;
+        }
+    }
+}
+
+/// A part of a larger text, parsed on its own.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    /// The byte offset of the chunk in the whole text.
+    pub offset: usize,
+    /// The root node of the chunk's untyped syntax tree.
+    pub root: SyntaxNode,
+}
+
+impl Chunk {
+    /// Start a traversal of the chunk's tree, with offsets in the whole text.
+    pub fn linked(&self) -> LinkedNode<'_> {
+        LinkedNode::with_offset(&self.root, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn test(text: &str, expected: &[&str]) {
+        let chunks: Vec<_> = parse_chunks(text.as_bytes()).map(Result::unwrap).collect();
+
+        let found: Vec<_> =
+            chunks.iter().map(|chunk| chunk.root.clone().into_text()).collect();
+        assert_eq!(found, expected);
+
+        let offsets: Vec<_> = chunks.iter().map(|chunk| chunk.offset).collect();
+        let mut offset = 0;
+        for (i, part) in expected.iter().enumerate() {
+            assert_eq!(offsets[i], offset);
+            offset += part.len();
+        }
+
+        let children: Vec<_> = chunks
+            .iter()
+            .flat_map(|chunk| chunk.root.children().cloned())
+            .collect();
+        assert_eq!(children, parse(text).children().cloned().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_chunks_split_at_top_level_parbreaks() {
+        test("", &[]);
+        test("= A\n\nb *c*\n\n\nd\n", &["= A\n\n", "b *c*\n\n\n", "d\n"]);
+        test("a\r\n\r\nb", &["a\r\n\r\n", "b"]);
+    }
+
+    #[test]
+    fn test_chunks_keep_nested_parbreaks() {
+        test("```\na\n\nb\n```\n\nc", &["```\na\n\nb\n```\n\n", "c"]);
+        test("#[a\n\nb]\n\nc", &["#[a\n\nb]\n\n", "c"]);
+        test("- a\n\n  b\n\n- c", &["- a\n\n  b\n\n", "- c"]);
+        test("/* a\n\nb", &["/* a\n\nb"]);
+        test("a\n\n  b", &["a\n\n  b"]);
+    }
+
+    #[test]
+    fn test_chunks_linked() {
+        let mut chunks = parse_chunks("a\n\n*b*".as_bytes());
+        let chunk = chunks.nth(1).unwrap().unwrap();
+        let linked = chunk.linked();
+        assert_eq!(linked.range(), 3..6);
+        assert_eq!(linked.leaf_at(5, crate::Side::Before).unwrap().text(), "b");
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{Span, SyntaxNode};
+
+/// A difference between two syntax trees.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Change {
+    /// A node that only exists in the new tree.
+    Inserted(Span),
+    /// A node that only exists in the old tree.
+    Removed(Span),
+    /// A node that was replaced, with the spans of its old and new version.
+    Changed(Span, Span),
+}
+
+/// Compute the differences between two syntax trees.
+///
+/// Nodes are compared structurally, ignoring their spans. Children of two
+/// nodes of the same kind are matched by skipping their common prefix and
+/// suffix and pairing up the rest in order. Only the outermost differing
+/// nodes are reported.
+pub fn diff(old: &SyntaxNode, new: &SyntaxNode) -> Vec<Change> {
+    let mut changes = vec![];
+    node(old, new, &mut changes);
+    changes
+}
+
+/// Compare two nodes.
+fn node(old: &SyntaxNode, new: &SyntaxNode, changes: &mut Vec<Change>) {
+    if old.spanless_eq(new) {
+        return;
+    }
+
+    let leaf = old.children().len() == 0 || new.children().len() == 0;
+    if old.kind() != new.kind() || leaf {
+        changes.push(Change::Changed(old.span(), new.span()));
+        return;
+    }
+
+    children(old.children().as_slice(), new.children().as_slice(), changes);
+}
+
+/// Compare two lists of children.
+fn children(old: &[SyntaxNode], new: &[SyntaxNode], changes: &mut Vec<Change>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a.spanless_eq(b)).count();
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a.spanless_eq(b))
+        .count();
+    let (old, new) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    for (a, b) in old.iter().zip(new) {
+        node(a, b, changes);
+    }
+
+    let paired = old.len().min(new.len());
+    changes.extend(old[paired..].iter().map(|node| Change::Removed(node.span())));
+    changes.extend(new[paired..].iter().map(|node| Change::Inserted(node.span())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Source;
+
+    #[track_caller]
+    fn test(prev: &str, next: &str, expected: &[&str]) {
+        let old = Source::detached(prev);
+        let new = Source::detached(next);
+        let text = |source: &Source, span| source.find(span).unwrap().get().clone();
+        let found: Vec<_> = diff(old.root(), new.root())
+            .into_iter()
+            .map(|change| match change {
+                Change::Inserted(span) => format!("+{}", text(&new, span).into_text()),
+                Change::Removed(span) => format!("-{}", text(&old, span).into_text()),
+                Change::Changed(a, b) => format!(
+                    "{}->{}",
+                    text(&old, a).into_text(),
+                    text(&new, b).into_text()
+                ),
+            })
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_diff() {
+        test("= A\nHello *world*", "= A\nHello *world*", &[]);
+        test("= A\nHello *world*", "= A\nHello *there*", &["world->there"]);
+        test("#f(1, 2)", "#f(1, 3, 4)", &["2->3", "+,", "+ ", "+4"]);
+        test("#f(1, 2)", "#g(1, 2)", &["f->g"]);
+        test("- a\n- b", "- a", &["-\n", "-- b"]);
+        test("#let x = 1", "#(1 + 2)", &["let x = 1->(1 + 2)"]);
+    }
+}
@@ -0,0 +1,449 @@
+use crate::{parse, split_newlines, SyntaxKind, SyntaxNode};
+
+/// Configuration for the source formatter.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FormatConfig {
+    /// The number of spaces per indentation level in code.
+    pub indent: usize,
+    /// The line width at which argument lists, arrays, dictionaries and
+    /// parameter lists are broken up into one item per line.
+    pub max_width: usize,
+    /// The maximum number of consecutive blank lines to keep.
+    pub blank_lines: usize,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self { indent: 2, max_width: 80, blank_lines: 1 }
+    }
+}
+
+/// Format Typst source code.
+///
+/// Code is re-indented and its spacing is normalized: One space around binary
+/// operators, `=` and `=>`, after commas, colons and keywords, and none just
+/// inside of parentheses, but one just inside of the braces of single-line
+/// code blocks. Markup is only trimmed, as its whitespace is
+/// meaningful. Raw text, strings and equations are kept as is.
+///
+/// Returns the text unchanged if it contains syntax errors.
+pub fn format(text: &str, config: &FormatConfig) -> String {
+    let root = parse(text);
+    if root.erroneous() {
+        return text.into();
+    }
+
+    let mut printer = Printer {
+        config,
+        out: String::new(),
+        levels: vec![],
+        flat: false,
+    };
+    printer.markup(&root);
+    printer.out
+}
+
+/// Emits the formatted text.
+struct Printer<'a> {
+    config: &'a FormatConfig,
+    out: String,
+    /// The indentation of the lines on which the currently open delimiters
+    /// in code start.
+    levels: Vec<usize>,
+    /// Whether collections are currently printed without breaking them up.
+    flat: bool,
+}
+
+impl Printer<'_> {
+    /// Print a node in markup mode.
+    fn markup(&mut self, node: &SyntaxNode) {
+        if node.children().len() == 0 {
+            self.out.push_str(node.text());
+            return;
+        }
+
+        let mut embedded = false;
+        for child in node.children() {
+            match child.kind() {
+                _ if embedded => self.code(child),
+                SyntaxKind::Space | SyntaxKind::Parbreak => self.markup_space(child),
+                SyntaxKind::Raw | SyntaxKind::Equation => self.verbatim(child),
+                _ => self.markup(child),
+            }
+            embedded = child.kind() == SyntaxKind::Hash;
+        }
+    }
+
+    /// Print whitespace in markup, keeping the indentation of the next line.
+    fn markup_space(&mut self, node: &SyntaxNode) {
+        let lines = split_newlines(node.text());
+        let newlines = lines.len() - 1;
+        if newlines == 0 {
+            self.out.push(' ');
+            return;
+        }
+
+        let newlines = if node.kind() == SyntaxKind::Parbreak {
+            newlines.min(self.config.blank_lines + 1).max(2)
+        } else {
+            newlines
+        };
+
+        self.newlines(newlines);
+        self.out.push_str(lines.last().unwrap());
+    }
+
+    /// Print a node in code mode.
+    fn code(&mut self, node: &SyntaxNode) {
+        match node.kind() {
+            SyntaxKind::Markup => return self.markup(node),
+            SyntaxKind::Raw
+            | SyntaxKind::Equation
+            | SyntaxKind::Math
+            | SyntaxKind::Interpolation => return self.verbatim(node),
+            SyntaxKind::CodeBlock if !node.clone().into_text().contains('\n') => {
+                return self.code_block(node)
+            }
+            _ => {}
+        }
+
+        if node.children().len() == 0 {
+            self.leaf(node);
+            return;
+        }
+
+        if self.flat || !is_breakable(node) {
+            self.code_children(node);
+            return;
+        }
+
+        // Try to fit the collection on one line first, so that outer
+        // collections are broken up before inner ones.
+        let start = self.out.len();
+        self.flat = true;
+        self.code_children(node);
+        self.flat = false;
+
+        let multiline = self.out[start..].contains('\n');
+        if multiline || self.column() > self.config.max_width {
+            self.out.truncate(start);
+            if multiline {
+                self.code_children(node);
+            } else {
+                self.broken(node);
+            }
+        }
+    }
+
+    /// Print the children of a node in code mode, normalizing the whitespace
+    /// between them.
+    fn code_children(&mut self, node: &SyntaxNode) {
+        let mut prev = None;
+        let mut space = None;
+        for child in node.children() {
+            if child.kind() == SyntaxKind::Space {
+                space = Some(child);
+                continue;
+            }
+
+            self.gap(node.kind(), prev, space.take(), child);
+            self.code(child);
+            prev = Some(child);
+        }
+    }
+
+    /// Print a code block on a single line, with one space just inside of each
+    /// brace unless it is empty.
+    fn code_block(&mut self, node: &SyntaxNode) {
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::Space => {}
+                SyntaxKind::Code => {
+                    let start = self.out.len();
+                    self.code(child);
+                    let body = self.out[start..].trim().to_string();
+                    self.out.truncate(start);
+                    if !body.is_empty() {
+                        self.out.push(' ');
+                        self.out.push_str(&body);
+                        self.out.push(' ');
+                    }
+                }
+                _ => self.code(child),
+            }
+        }
+    }
+
+    /// Print a collection with one item per line.
+    fn broken(&mut self, node: &SyntaxNode) {
+        let level = self.line_indent();
+        let indent = " ".repeat(level + self.config.indent);
+        let mut open = false;
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::LeftParen if !open => {
+                    open = true;
+                    self.out.push('(');
+                    self.levels.push(level);
+                }
+                SyntaxKind::RightParen if open => {
+                    open = false;
+                    self.out.push('\n');
+                    self.out.push_str(&" ".repeat(level));
+                    self.out.push(')');
+                    self.levels.pop();
+                }
+                SyntaxKind::Space | SyntaxKind::Comma if open => {}
+                _ if open => {
+                    self.out.push('\n');
+                    self.out.push_str(&indent);
+                    self.code(child);
+                    self.out.push(',');
+                }
+                _ => self.code(child),
+            }
+        }
+    }
+
+    /// Print the whitespace between two children of a code node.
+    fn gap(
+        &mut self,
+        parent: SyntaxKind,
+        prev: Option<&SyntaxNode>,
+        space: Option<&SyntaxNode>,
+        next: &SyntaxNode,
+    ) {
+        if let Some(space) = space {
+            let lines = split_newlines(space.text());
+            if lines.len() > 1 {
+                self.newlines((lines.len() - 1).min(self.config.blank_lines + 1));
+                let indent = match self.levels.last() {
+                    Some(&level) if is_closing(next.kind()) => level,
+                    Some(&level) => level + self.config.indent,
+                    None => lines.last().unwrap().len(),
+                };
+                self.out.push_str(&" ".repeat(indent));
+                return;
+            }
+        }
+
+        let Some(prev) = prev else {
+            if space.is_some() {
+                self.out.push(' ');
+            }
+            return;
+        };
+
+        if spaced(parent, prev.kind(), next.kind()).unwrap_or(space.is_some()) {
+            self.out.push(' ');
+        }
+    }
+
+    /// Print a leaf in code mode.
+    fn leaf(&mut self, node: &SyntaxNode) {
+        let kind = node.kind();
+        if is_closing(kind) {
+            self.levels.pop();
+        }
+
+        if is_opening(kind) {
+            self.levels.push(self.line_indent());
+        }
+
+        self.out.push_str(node.text());
+    }
+
+    /// Print a node exactly as it is.
+    fn verbatim(&mut self, node: &SyntaxNode) {
+        self.out.push_str(&node.clone().into_text());
+    }
+
+    /// Print line breaks, removing trailing whitespace from the current line.
+    fn newlines(&mut self, count: usize) {
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+        for _ in 0..count {
+            self.out.push('\n');
+        }
+    }
+
+    /// The width of the current line.
+    fn column(&self) -> usize {
+        self.current_line().chars().count()
+    }
+
+    /// The indentation of the current line.
+    fn line_indent(&self) -> usize {
+        let line = self.current_line();
+        line.len() - line.trim_start_matches([' ', '\t']).len()
+    }
+
+    /// The text of the last line printed so far.
+    fn current_line(&self) -> &str {
+        let start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        &self.out[start..]
+    }
+}
+
+/// Whether there should be a space between two children of a code node.
+///
+/// Returns `None` if the existing spacing should be kept.
+fn spaced(parent: SyntaxKind, prev: SyntaxKind, next: SyntaxKind) -> Option<bool> {
+    if prev.is_trivia() || next.is_trivia() || is_closing(next) && !is_paren(next) {
+        return None;
+    }
+
+    let collection = matches!(
+        parent,
+        SyntaxKind::Args
+            | SyntaxKind::Array
+            | SyntaxKind::Dict
+            | SyntaxKind::Params
+            | SyntaxKind::Parenthesized
+            | SyntaxKind::Destructuring
+    );
+
+    Some(match (prev, next) {
+        (_, SyntaxKind::Comma | SyntaxKind::Semicolon | SyntaxKind::Colon) => false,
+        (SyntaxKind::LeftParen, _) | (_, SyntaxKind::RightParen) if collection => false,
+        (SyntaxKind::Comma | SyntaxKind::Semicolon | SyntaxKind::Colon, _) => true,
+        (_, SyntaxKind::Args) => false,
+        (_, SyntaxKind::Eq | SyntaxKind::Arrow) => true,
+        (SyntaxKind::Eq | SyntaxKind::Arrow, _) => true,
+        (_, SyntaxKind::Else | SyntaxKind::In | SyntaxKind::As) => true,
+        (_, SyntaxKind::If) if parent == SyntaxKind::SetRule => true,
+        (
+            SyntaxKind::Not
+            | SyntaxKind::Let
+            | SyntaxKind::Set
+            | SyntaxKind::Show
+            | SyntaxKind::Context
+            | SyntaxKind::If
+            | SyntaxKind::Else
+            | SyntaxKind::For
+            | SyntaxKind::In
+            | SyntaxKind::While
+            | SyntaxKind::Return
+            | SyntaxKind::Import
+            | SyntaxKind::Include
+            | SyntaxKind::As,
+            _,
+        ) => true,
+        _ => matches!(
+            parent,
+            SyntaxKind::Binary
+                | SyntaxKind::Conditional
+                | SyntaxKind::WhileLoop
+                | SyntaxKind::ForLoop
+        )
+        .then_some(true)?,
+    })
+}
+
+/// Whether a node can be broken up into one item per line.
+fn is_breakable(node: &SyntaxNode) -> bool {
+    let kinds = || node.children().map(SyntaxNode::kind);
+    let items = kinds()
+        .filter(|&kind| {
+            !kind.is_trivia()
+                && !matches!(
+                    kind,
+                    SyntaxKind::LeftParen
+                        | SyntaxKind::RightParen
+                        | SyntaxKind::Comma
+                        | SyntaxKind::Colon
+                        | SyntaxKind::ContentBlock
+                )
+        })
+        .count();
+
+    matches!(
+        node.kind(),
+        SyntaxKind::Args | SyntaxKind::Array | SyntaxKind::Dict | SyntaxKind::Params
+    ) && items >= 2
+        && kinds().any(|kind| kind == SyntaxKind::LeftParen)
+        && !kinds().any(|kind| {
+            matches!(kind, SyntaxKind::LineComment | SyntaxKind::BlockComment)
+        })
+}
+
+/// Whether this is an opening delimiter in code.
+fn is_opening(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::LeftParen | SyntaxKind::LeftBrace | SyntaxKind::LeftBracket
+    )
+}
+
+/// Whether this is a parenthesis.
+fn is_paren(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::LeftParen | SyntaxKind::RightParen)
+}
+
+/// Whether this is a closing delimiter in code.
+fn is_closing(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::RightParen | SyntaxKind::RightBrace | SyntaxKind::RightBracket
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn test(text: &str, goal: &str) {
+        let config = FormatConfig { max_width: 30, ..FormatConfig::default() };
+        let formatted = format(text, &config);
+        assert_eq!(formatted, goal);
+        assert_eq!(format(&formatted, &config), formatted);
+    }
+
+    #[test]
+    fn test_format_code() {
+        test("#f(a,b ,  c)", "#f(a, b, c)");
+        test("#let x=1+2*3", "#let x = 1 + 2 * 3");
+        test("#let f( x , y )=x", "#let f(x, y) = x");
+        test("#let g = ( x )=>x", "#let g = (x) => x");
+        test("#{ a;b;}", "#{ a; b; }");
+        test("#{a}", "#{ a }");
+        test("#{  }", "#{}");
+        test("#(a:1,b:( 2, ))", "#(a: 1, b: (2,))");
+        test("#if x>1{a}else{b}", "#if x > 1 { a } else { b }");
+        test("#set text(red)if x", "#set text(red) if x");
+        test("#show heading:it=>it.body", "#show heading: it => it.body");
+        test("#import \"a.typ\":x,y", "#import \"a.typ\": x, y");
+        test("#{\nlet x = 1   \n\n\n  x\n}", "#{\n  let x = 1\n\n  x\n}");
+        test("- a\n  #(\n1,\n  2)", "- a\n  #(\n    1,\n    2)");
+    }
+
+    #[test]
+    fn test_format_breaking() {
+        test(
+            "#figure(image(\"a.png\"), caption: [A picture])",
+            "#figure(\n  image(\"a.png\"),\n  caption: [A picture],\n)",
+        );
+        test(
+            "#f(aaaaaaaaaaaa, bbbbbbbbbbbb)[c]",
+            "#f(\n  aaaaaaaaaaaa,\n  bbbbbbbbbbbb,\n)[c]",
+        );
+        test(
+            "#f(a, g(bbbbbbbbbb, cccccccccc))",
+            "#f(\n  a,\n  g(bbbbbbbbbb, cccccccccc),\n)",
+        );
+        test(
+            "A long line of text #link(\"https://typst.app\")",
+            "A long line of text #link(\"https://typst.app\")",
+        );
+    }
+
+    #[test]
+    fn test_format_markup() {
+        test("a  b   \nc\n\n\n\nd", "a b\nc\n\nd");
+        test("- a\n  - b", "- a\n  - b");
+        test("```\n  x  = 1  \n```", "```\n  x  = 1  \n```");
+        test("$a+b  ,c$ #\"x  {1+2}\"", "$a+b  ,c$ #\"x  {1+2}\"");
+        test("#f(a", "#f(a");
+    }
+}
@@ -0,0 +1,344 @@
+//! Which kinds of nodes can contain which children.
+
+use crate::set::{SyntaxSet, BINARY_OP, UNARY_OP};
+use crate::SyntaxKind;
+
+/// The kinds of the direct children that a node of the given kind can have,
+/// apart from errors.
+pub const fn children(kind: SyntaxKind) -> SyntaxSet {
+    match kind {
+        SyntaxKind::Markup => MARKUP,
+        SyntaxKind::Strong => {
+            SyntaxSet::new().add(SyntaxKind::Star).add(SyntaxKind::Markup)
+        }
+        SyntaxKind::Emph => {
+            SyntaxSet::new().add(SyntaxKind::Underscore).add(SyntaxKind::Markup)
+        }
+        SyntaxKind::Strike => {
+            SyntaxSet::new().add(SyntaxKind::StrikeDelim).add(SyntaxKind::Markup)
+        }
+        SyntaxKind::Raw => SyntaxSet::new()
+            .add(SyntaxKind::RawDelim)
+            .add(SyntaxKind::RawLang)
+            .add(SyntaxKind::RawMeta)
+            .add(SyntaxKind::RawTrimmed)
+            .add(SyntaxKind::Text)
+            .add(SyntaxKind::Space)
+            .add(SyntaxKind::Label),
+        SyntaxKind::Ref => SyntaxSet::new()
+            .add(SyntaxKind::RefMarker)
+            .add(SyntaxKind::ContentBlock),
+        SyntaxKind::Footnote => SyntaxSet::new()
+            .add(SyntaxKind::FootnoteMarker)
+            .add(SyntaxKind::ContentBlock),
+        SyntaxKind::Heading => SyntaxSet::new()
+            .add(SyntaxKind::HeadingMarker)
+            .add(SyntaxKind::Space)
+            .add(SyntaxKind::Markup)
+            .add(SyntaxKind::Label),
+        SyntaxKind::ListItem => SyntaxSet::new()
+            .add(SyntaxKind::ListMarker)
+            .add(SyntaxKind::ListCheckbox)
+            .add(SyntaxKind::Space)
+            .add(SyntaxKind::Markup),
+        SyntaxKind::EnumItem => SyntaxSet::new()
+            .add(SyntaxKind::EnumMarker)
+            .add(SyntaxKind::Space)
+            .add(SyntaxKind::Markup),
+        SyntaxKind::TermItem => SyntaxSet::new()
+            .add(SyntaxKind::TermMarker)
+            .add(SyntaxKind::Space)
+            .add(SyntaxKind::Markup)
+            .add(SyntaxKind::Colon),
+        SyntaxKind::Table => SyntaxSet::new()
+            .add(SyntaxKind::TableRow)
+            .add(SyntaxKind::TableSeparator)
+            .add(SyntaxKind::Space),
+        SyntaxKind::TableRow => SyntaxSet::new()
+            .add(SyntaxKind::TableCell)
+            .add(SyntaxKind::TableDelim)
+            .add(SyntaxKind::Space),
+        SyntaxKind::TableCell => SyntaxSet::new().add(SyntaxKind::Markup),
+        SyntaxKind::Equation => SyntaxSet::new()
+            .add(SyntaxKind::Dollar)
+            .add(SyntaxKind::Space)
+            .add(SyntaxKind::Math)
+            .add(SyntaxKind::Label),
+        SyntaxKind::Math | SyntaxKind::MathDelimited => MATH,
+        SyntaxKind::MathAttach => MATH.add(SyntaxKind::Underscore).add(SyntaxKind::Hat),
+        SyntaxKind::MathPrimes => SyntaxSet::new().add(SyntaxKind::Prime),
+        SyntaxKind::MathFrac => MATH.add(SyntaxKind::Slash),
+        SyntaxKind::MathRoot => MATH.add(SyntaxKind::Root),
+        SyntaxKind::Code => CODE.union(TRIVIA).add(SyntaxKind::Semicolon),
+        SyntaxKind::CodeBlock => TRIVIA
+            .add(SyntaxKind::LeftBrace)
+            .add(SyntaxKind::Code)
+            .add(SyntaxKind::RightBrace),
+        SyntaxKind::ContentBlock => SyntaxSet::new()
+            .add(SyntaxKind::LeftBracket)
+            .add(SyntaxKind::Markup)
+            .add(SyntaxKind::RightBracket),
+        SyntaxKind::Parenthesized => CODE
+            .union(TRIVIA)
+            .add(SyntaxKind::LeftParen)
+            .add(SyntaxKind::RightParen),
+        SyntaxKind::Array => MATH
+            .union(TRIVIA)
+            .add(SyntaxKind::LeftParen)
+            .add(SyntaxKind::RightParen)
+            .add(SyntaxKind::Comma)
+            .add(SyntaxKind::Spread),
+        SyntaxKind::Dict => TRIVIA
+            .add(SyntaxKind::LeftParen)
+            .add(SyntaxKind::RightParen)
+            .add(SyntaxKind::Colon)
+            .add(SyntaxKind::Comma)
+            .add(SyntaxKind::Named)
+            .add(SyntaxKind::Keyed)
+            .add(SyntaxKind::Spread),
+        SyntaxKind::Named => {
+            MATH.union(TRIVIA).add(SyntaxKind::Colon).add(SyntaxKind::Underscore)
+        }
+        SyntaxKind::Keyed => CODE.union(TRIVIA).add(SyntaxKind::Colon),
+        SyntaxKind::Unary => CODE.union(TRIVIA).union(UNARY_OP),
+        SyntaxKind::Binary => CODE.union(TRIVIA).union(BINARY_OP).add(SyntaxKind::Not),
+        SyntaxKind::FieldAccess => {
+            CODE.union(TRIVIA).add(SyntaxKind::MathIdent).add(SyntaxKind::Dot)
+        }
+        SyntaxKind::FuncCall => {
+            CODE.union(TRIVIA).add(SyntaxKind::MathIdent).add(SyntaxKind::Args)
+        }
+        SyntaxKind::Args => MATH
+            .union(TRIVIA)
+            .add(SyntaxKind::LeftParen)
+            .add(SyntaxKind::RightParen)
+            .add(SyntaxKind::Comma)
+            .add(SyntaxKind::Semicolon)
+            .add(SyntaxKind::Named)
+            .add(SyntaxKind::Spread),
+        SyntaxKind::Spread => {
+            CODE.union(TRIVIA).add(SyntaxKind::Dots).add(SyntaxKind::Underscore)
+        }
+        SyntaxKind::Closure => CODE
+            .union(TRIVIA)
+            .add(SyntaxKind::Params)
+            .add(SyntaxKind::Eq)
+            .add(SyntaxKind::Arrow),
+        SyntaxKind::Params => PATTERN
+            .add(SyntaxKind::LeftParen)
+            .add(SyntaxKind::RightParen)
+            .add(SyntaxKind::Comma)
+            .add(SyntaxKind::Named)
+            .add(SyntaxKind::Spread),
+        SyntaxKind::LetBinding => {
+            CODE.union(PATTERN).add(SyntaxKind::Let).add(SyntaxKind::Eq)
+        }
+        SyntaxKind::SetRule => CODE
+            .union(TRIVIA)
+            .add(SyntaxKind::Set)
+            .add(SyntaxKind::Args)
+            .add(SyntaxKind::If),
+        SyntaxKind::ShowRule => {
+            CODE.union(TRIVIA).add(SyntaxKind::Show).add(SyntaxKind::Colon)
+        }
+        SyntaxKind::Contextual => CODE.union(TRIVIA).add(SyntaxKind::Context),
+        SyntaxKind::Conditional => {
+            CODE.union(TRIVIA).add(SyntaxKind::If).add(SyntaxKind::Else)
+        }
+        SyntaxKind::WhileLoop => CODE.union(TRIVIA).add(SyntaxKind::While),
+        SyntaxKind::ForLoop => {
+            CODE.union(PATTERN).add(SyntaxKind::For).add(SyntaxKind::In)
+        }
+        SyntaxKind::ModuleImport => CODE
+            .union(TRIVIA)
+            .add(SyntaxKind::Import)
+            .add(SyntaxKind::Colon)
+            .add(SyntaxKind::Star)
+            .add(SyntaxKind::As)
+            .add(SyntaxKind::ImportItems),
+        SyntaxKind::ImportItems => TRIVIA
+            .add(SyntaxKind::Ident)
+            .add(SyntaxKind::RenamedImportItem)
+            .add(SyntaxKind::Comma),
+        SyntaxKind::RenamedImportItem => {
+            TRIVIA.add(SyntaxKind::Ident).add(SyntaxKind::As)
+        }
+        SyntaxKind::ModuleInclude => CODE.union(TRIVIA).add(SyntaxKind::Include),
+        SyntaxKind::LoopBreak => SyntaxSet::new().add(SyntaxKind::Break),
+        SyntaxKind::LoopContinue => SyntaxSet::new().add(SyntaxKind::Continue),
+        SyntaxKind::FuncReturn => CODE.union(TRIVIA).add(SyntaxKind::Return),
+        SyntaxKind::Destructuring => CODE
+            .union(PATTERN)
+            .add(SyntaxKind::LeftParen)
+            .add(SyntaxKind::RightParen)
+            .add(SyntaxKind::Comma)
+            .add(SyntaxKind::Named)
+            .add(SyntaxKind::Spread),
+        SyntaxKind::DestructAssignment => CODE.union(PATTERN).add(SyntaxKind::Eq),
+        SyntaxKind::Interpolation => SyntaxSet::new()
+            .add(SyntaxKind::StrDelim)
+            .add(SyntaxKind::StrPart)
+            .add(SyntaxKind::CodeBlock),
+        _ => SyntaxSet::new(),
+    }
+}
+
+/// Trivia that can appear between the parts of code.
+const TRIVIA: SyntaxSet = SyntaxSet::new()
+    .add(SyntaxKind::Space)
+    .add(SyntaxKind::LineComment)
+    .add(SyntaxKind::BlockComment);
+
+/// Nodes that a code expression can result in, including the `not` that is
+/// left over when a `not in` lacks the `in`.
+const CODE: SyntaxSet = SyntaxSet::new()
+    .add(SyntaxKind::Ident)
+    .add(SyntaxKind::None)
+    .add(SyntaxKind::Auto)
+    .add(SyntaxKind::Bool)
+    .add(SyntaxKind::Int)
+    .add(SyntaxKind::Float)
+    .add(SyntaxKind::Numeric)
+    .add(SyntaxKind::Str)
+    .add(SyntaxKind::Interpolation)
+    .add(SyntaxKind::Label)
+    .add(SyntaxKind::Raw)
+    .add(SyntaxKind::Equation)
+    .add(SyntaxKind::CodeBlock)
+    .add(SyntaxKind::ContentBlock)
+    .add(SyntaxKind::Parenthesized)
+    .add(SyntaxKind::Array)
+    .add(SyntaxKind::Dict)
+    .add(SyntaxKind::Unary)
+    .add(SyntaxKind::Binary)
+    .add(SyntaxKind::FieldAccess)
+    .add(SyntaxKind::FuncCall)
+    .add(SyntaxKind::Closure)
+    .add(SyntaxKind::LetBinding)
+    .add(SyntaxKind::SetRule)
+    .add(SyntaxKind::ShowRule)
+    .add(SyntaxKind::Contextual)
+    .add(SyntaxKind::Conditional)
+    .add(SyntaxKind::WhileLoop)
+    .add(SyntaxKind::ForLoop)
+    .add(SyntaxKind::ModuleImport)
+    .add(SyntaxKind::ModuleInclude)
+    .add(SyntaxKind::LoopBreak)
+    .add(SyntaxKind::LoopContinue)
+    .add(SyntaxKind::FuncReturn)
+    .add(SyntaxKind::Destructuring)
+    .add(SyntaxKind::DestructAssignment)
+    .add(SyntaxKind::Not);
+
+/// Nodes that can appear in markup.
+const MARKUP: SyntaxSet = CODE
+    .union(TRIVIA)
+    .add(SyntaxKind::Text)
+    .add(SyntaxKind::Linebreak)
+    .add(SyntaxKind::Parbreak)
+    .add(SyntaxKind::Rule)
+    .add(SyntaxKind::Escape)
+    .add(SyntaxKind::Shorthand)
+    .add(SyntaxKind::Shortcode)
+    .add(SyntaxKind::SmartQuote)
+    .add(SyntaxKind::Strong)
+    .add(SyntaxKind::Emph)
+    .add(SyntaxKind::Strike)
+    .add(SyntaxKind::Link)
+    .add(SyntaxKind::Ref)
+    .add(SyntaxKind::Footnote)
+    .add(SyntaxKind::Heading)
+    .add(SyntaxKind::ListItem)
+    .add(SyntaxKind::EnumItem)
+    .add(SyntaxKind::TermItem)
+    .add(SyntaxKind::Table)
+    .add(SyntaxKind::Hash)
+    .add(SyntaxKind::Semicolon);
+
+/// Nodes that can appear in math.
+const MATH: SyntaxSet = CODE
+    .union(TRIVIA)
+    .add(SyntaxKind::Text)
+    .add(SyntaxKind::Linebreak)
+    .add(SyntaxKind::Escape)
+    .add(SyntaxKind::Shorthand)
+    .add(SyntaxKind::Math)
+    .add(SyntaxKind::MathIdent)
+    .add(SyntaxKind::MathAlignPoint)
+    .add(SyntaxKind::MathDelimited)
+    .add(SyntaxKind::MathAttach)
+    .add(SyntaxKind::MathPrimes)
+    .add(SyntaxKind::MathFrac)
+    .add(SyntaxKind::MathRoot)
+    .add(SyntaxKind::LeftParen)
+    .add(SyntaxKind::RightParen)
+    .add(SyntaxKind::Hash)
+    .add(SyntaxKind::Semicolon);
+
+/// Nodes that can appear in patterns.
+const PATTERN: SyntaxSet = TRIVIA
+    .add(SyntaxKind::Ident)
+    .add(SyntaxKind::Underscore)
+    .add(SyntaxKind::Destructuring);
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{parse, parse_code, parse_math, SyntaxNode};
+
+    #[track_caller]
+    fn check(node: &SyntaxNode) {
+        for child in node.children() {
+            assert!(
+                node.kind().can_contain(child.kind()),
+                "{:?} contains {:?} in {:?}",
+                node.kind(),
+                child.kind(),
+                node.clone().into_text(),
+            );
+            check(child);
+        }
+    }
+
+    fn check_dir(dir: &Path) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                check_dir(&path);
+            } else if path.extension().is_some_and(|ext| ext == "typ") {
+                let text = std::fs::read_to_string(&path).unwrap();
+                for (end, _) in text.match_indices('\n').step_by(8) {
+                    check(&parse(&text[..end]));
+                }
+                check(&parse(&text));
+                check(&parse_code(&text));
+                check(&parse_math(&text));
+            }
+        }
+    }
+
+    #[test]
+    fn test_grammar_kinds() {
+        for (i, &kind) in SyntaxKind::ALL.iter().enumerate() {
+            assert_eq!(kind as usize, i);
+        }
+        assert_eq!(SyntaxKind::ALL.last(), Some(&SyntaxKind::Eof));
+        assert!(SyntaxKind::Text.children().next().is_none());
+        assert!(!SyntaxKind::Text.can_contain(SyntaxKind::Error));
+        assert!(SyntaxKind::LoopBreak
+            .children()
+            .eq([SyntaxKind::Break, SyntaxKind::Error]));
+    }
+
+    #[test]
+    fn test_grammar_matches_parser() {
+        // The test files cover most of the syntax, and a sample of their
+        // prefixes much of the error recovery.
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/typ");
+        if dir.exists() {
+            check_dir(&dir);
+        }
+    }
+}
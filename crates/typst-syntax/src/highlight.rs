@@ -13,6 +13,8 @@ pub enum Tag {
     Strong,
     /// Emphasized markup.
     Emph,
+    /// Struck-through markup.
+    Strike,
     /// A hyperlink.
     Link,
     /// Raw text.
@@ -57,6 +59,7 @@ impl Tag {
         Self::Escape,
         Self::Strong,
         Self::Emph,
+        Self::Strike,
         Self::Link,
         Self::Raw,
         Self::Label,
@@ -84,6 +87,7 @@ impl Tag {
             Self::Escape => "constant.character.escape.typst",
             Self::Strong => "markup.bold.typst",
             Self::Emph => "markup.italic.typst",
+            Self::Strike => "markup.strikethrough.typst",
             Self::Link => "markup.underline.link.typst",
             Self::Raw => "markup.raw.typst",
             Self::MathDelimiter => "punctuation.definition.math.typst",
@@ -111,6 +115,7 @@ impl Tag {
             Self::Escape => "typ-escape",
             Self::Strong => "typ-strong",
             Self::Emph => "typ-emph",
+            Self::Strike => "typ-strike",
             Self::Link => "typ-link",
             Self::Raw => "typ-raw",
             Self::Label => "typ-label",
@@ -136,6 +141,11 @@ impl Tag {
 /// Returns `None` if the node should not be highlighted.
 pub fn highlight(node: &LinkedNode) -> Option<Tag> {
     match node.kind() {
+        SyntaxKind::Eof => None,
+        SyntaxKind::Error => Some(Tag::Error),
+        SyntaxKind::LineComment => Some(Tag::Comment),
+        SyntaxKind::BlockComment => Some(Tag::Comment),
+
         SyntaxKind::Markup
             if node.parent_kind() == Some(SyntaxKind::TermItem)
                 && node.next_sibling_kind() == Some(SyntaxKind::Colon) =>
@@ -146,28 +156,40 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::Text => None,
         SyntaxKind::Space => None,
         SyntaxKind::Linebreak => Some(Tag::Escape),
+        SyntaxKind::Rule => Some(Tag::Punctuation),
         SyntaxKind::Parbreak => None,
         SyntaxKind::Escape => Some(Tag::Escape),
         SyntaxKind::Shorthand => Some(Tag::Escape),
+        SyntaxKind::Shortcode => Some(Tag::Escape),
         SyntaxKind::SmartQuote => None,
         SyntaxKind::Strong => Some(Tag::Strong),
         SyntaxKind::Emph => Some(Tag::Emph),
+        SyntaxKind::Strike => Some(Tag::Strike),
         SyntaxKind::Raw => Some(Tag::Raw),
         SyntaxKind::RawLang => None,
+        SyntaxKind::RawMeta => None,
         SyntaxKind::RawTrimmed => None,
         SyntaxKind::RawDelim => None,
         SyntaxKind::Link => Some(Tag::Link),
         SyntaxKind::Label => Some(Tag::Label),
         SyntaxKind::Ref => Some(Tag::Ref),
         SyntaxKind::RefMarker => None,
+        SyntaxKind::Footnote => None,
+        SyntaxKind::FootnoteMarker => Some(Tag::Punctuation),
         SyntaxKind::Heading => Some(Tag::Heading),
         SyntaxKind::HeadingMarker => None,
         SyntaxKind::ListItem => None,
         SyntaxKind::ListMarker => Some(Tag::ListMarker),
+        SyntaxKind::ListCheckbox => Some(Tag::ListMarker),
         SyntaxKind::EnumItem => None,
         SyntaxKind::EnumMarker => Some(Tag::ListMarker),
         SyntaxKind::TermItem => None,
         SyntaxKind::TermMarker => Some(Tag::ListMarker),
+        SyntaxKind::Table => None,
+        SyntaxKind::TableRow => None,
+        SyntaxKind::TableCell => None,
+        SyntaxKind::TableDelim => Some(Tag::Punctuation),
+        SyntaxKind::TableSeparator => Some(Tag::Punctuation),
         SyntaxKind::Equation => None,
 
         SyntaxKind::Math => None,
@@ -197,6 +219,7 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
             Some(SyntaxKind::MathAttach) => Some(Tag::MathOperator),
             _ => None,
         },
+        SyntaxKind::StrikeDelim => None,
         SyntaxKind::Dollar => Some(Tag::MathDelimiter),
         SyntaxKind::Plus => Some(Tag::Operator),
         SyntaxKind::Minus => Some(Tag::Operator),
@@ -253,6 +276,9 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::Float => Some(Tag::Number),
         SyntaxKind::Numeric => Some(Tag::Number),
         SyntaxKind::Str => Some(Tag::String),
+        SyntaxKind::StrDelim => Some(Tag::String),
+        SyntaxKind::StrPart => Some(Tag::String),
+        SyntaxKind::Interpolation => None,
         SyntaxKind::CodeBlock => None,
         SyntaxKind::ContentBlock => None,
         SyntaxKind::Parenthesized => None,
@@ -284,11 +310,6 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::FuncReturn => None,
         SyntaxKind::Destructuring => None,
         SyntaxKind::DestructAssignment => None,
-
-        SyntaxKind::LineComment => Some(Tag::Comment),
-        SyntaxKind::BlockComment => Some(Tag::Comment),
-        SyntaxKind::Error => Some(Tag::Error),
-        SyntaxKind::Eof => None,
     }
 }
 
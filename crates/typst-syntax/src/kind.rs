@@ -1,7 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::grammar;
+
 /// A syntactical building block of a Typst file.
 ///
 /// Can be created by the lexer or by the parser.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 #[repr(u8)]
 pub enum SyntaxKind {
     /// The contents of a file or content block.
@@ -15,21 +21,29 @@ pub enum SyntaxKind {
     Linebreak,
     /// A paragraph break, indicated by one or multiple blank lines.
     Parbreak,
-    /// An escape sequence: `\#`, `\u{1F5FA}`.
+    /// A horizontal rule, three or more dashes on their own line: `---`.
+    Rule,
+    /// An escape sequence: `\#`, `\u{1F5FA}`, `\u{name:EM DASH}`.
     Escape,
     /// A shorthand for a unicode codepoint. For example, `~` for non-breaking
     /// space or `-?` for a soft hyphen.
     Shorthand,
+    /// An emoji shortcode: `:face.grin:`.
+    Shortcode,
     /// A smart quote: `'` or `"`.
     SmartQuote,
     /// Strong content: `*Strong*`.
     Strong,
     /// Emphasized content: `_Emphasized_`.
     Emph,
-    /// Raw text with optional syntax highlighting: `` `...` ``.
+    /// Struck-through content: `~~Struck~~`.
+    Strike,
+    /// Raw text with optional syntax highlighting and label: `` `...` ``.
     Raw,
     /// A language tag at the start of raw text: ``typ ``.
     RawLang,
+    /// Options following the language tag of raw text: ``rust:3..10{5} ``.
+    RawMeta,
     /// A raw delimiter consisting of 1 or 3+ backticks: `` ` ``.
     RawDelim,
     /// A sequence of whitespace to ignore in a raw block: `    `.
@@ -42,7 +56,11 @@ pub enum SyntaxKind {
     Ref,
     /// Introduces a reference: `@target`.
     RefMarker,
-    /// A section heading: `= Introduction`.
+    /// A footnote: `^[..]`.
+    Footnote,
+    /// Introduces a footnote: `^`.
+    FootnoteMarker,
+    /// A section heading, optionally with a label: `= Introduction <intro>`.
     Heading,
     /// Introduces a section heading: `=`, `==`, ...
     HeadingMarker,
@@ -50,6 +68,8 @@ pub enum SyntaxKind {
     ListItem,
     /// Introduces a list item: `-`.
     ListMarker,
+    /// A checkbox at the start of a list item: `[ ]` or `[x]`.
+    ListCheckbox,
     /// An item in an enumeration (numbered list): `+ ...` or `1. ...`.
     EnumItem,
     /// Introduces an enumeration item: `+`, `1.`.
@@ -58,7 +78,18 @@ pub enum SyntaxKind {
     TermItem,
     /// Introduces a term item: `/`.
     TermMarker,
-    /// A mathematical equation: `$x$`, `$ x^2 $`.
+    /// A table in markup: a header row, a separator line like `|---|---|`, and
+    /// body rows.
+    Table,
+    /// A row in a markup table: `| a | b |`.
+    TableRow,
+    /// A cell in a markup table row: `a`.
+    TableCell,
+    /// Starts a table row and separates its cells: `|`.
+    TableDelim,
+    /// Separates the header of a markup table from its body: `|---|:-:|`.
+    TableSeparator,
+    /// A mathematical equation, optionally with a label: `$x$`, `$ x^2 $ <eq>`.
     Equation,
 
     /// The contents of a mathematical equation: `x^2 + 1`.
@@ -106,6 +137,8 @@ pub enum SyntaxKind {
     Star,
     /// Toggles emphasized text and indicates a subscript in math: `_`.
     Underscore,
+    /// Toggles struck-through text: `~~`.
+    StrikeDelim,
     /// Starts and ends a mathematical equation: `$`.
     Dollar,
     /// The unary plus and binary addition operator: `+`.
@@ -202,8 +235,14 @@ pub enum SyntaxKind {
     Float,
     /// A numeric value with a unit: `12pt`, `3cm`, `2em`, `90deg`, `50%`.
     Numeric,
-    /// A quoted string: `"..."`.
+    /// A quoted string: `"..."` or `r"..."`.
     Str,
+    /// The quotes delimiting an interpolated string: `f"` and `"`.
+    StrDelim,
+    /// A literal piece of an interpolated string.
+    StrPart,
+    /// A string with embedded code: `f"Hello {name}!"`.
+    Interpolation,
     /// A code block: `{ let x = 1; x + 2 }`.
     CodeBlock,
     /// A content block: `[*Hi* there!]`.
@@ -278,6 +317,175 @@ pub enum SyntaxKind {
 }
 
 impl SyntaxKind {
+    /// All syntax kinds, in declaration order.
+    pub const ALL: &'static [Self] = &[
+        Self::Markup,
+        Self::Text,
+        Self::Space,
+        Self::Linebreak,
+        Self::Parbreak,
+        Self::Rule,
+        Self::Escape,
+        Self::Shorthand,
+        Self::Shortcode,
+        Self::SmartQuote,
+        Self::Strong,
+        Self::Emph,
+        Self::Strike,
+        Self::Raw,
+        Self::RawLang,
+        Self::RawMeta,
+        Self::RawDelim,
+        Self::RawTrimmed,
+        Self::Link,
+        Self::Label,
+        Self::Ref,
+        Self::RefMarker,
+        Self::Footnote,
+        Self::FootnoteMarker,
+        Self::Heading,
+        Self::HeadingMarker,
+        Self::ListItem,
+        Self::ListMarker,
+        Self::ListCheckbox,
+        Self::EnumItem,
+        Self::EnumMarker,
+        Self::TermItem,
+        Self::TermMarker,
+        Self::Table,
+        Self::TableRow,
+        Self::TableCell,
+        Self::TableDelim,
+        Self::TableSeparator,
+        Self::Equation,
+        Self::Math,
+        Self::MathIdent,
+        Self::MathAlignPoint,
+        Self::MathDelimited,
+        Self::MathAttach,
+        Self::MathPrimes,
+        Self::MathFrac,
+        Self::MathRoot,
+        Self::Hash,
+        Self::LeftBrace,
+        Self::RightBrace,
+        Self::LeftBracket,
+        Self::RightBracket,
+        Self::LeftParen,
+        Self::RightParen,
+        Self::Comma,
+        Self::Semicolon,
+        Self::Colon,
+        Self::Star,
+        Self::Underscore,
+        Self::StrikeDelim,
+        Self::Dollar,
+        Self::Plus,
+        Self::Minus,
+        Self::Slash,
+        Self::Hat,
+        Self::Prime,
+        Self::Dot,
+        Self::Eq,
+        Self::EqEq,
+        Self::ExclEq,
+        Self::Lt,
+        Self::LtEq,
+        Self::Gt,
+        Self::GtEq,
+        Self::PlusEq,
+        Self::HyphEq,
+        Self::StarEq,
+        Self::SlashEq,
+        Self::Dots,
+        Self::Arrow,
+        Self::Root,
+        Self::Not,
+        Self::And,
+        Self::Or,
+        Self::None,
+        Self::Auto,
+        Self::Let,
+        Self::Set,
+        Self::Show,
+        Self::Context,
+        Self::If,
+        Self::Else,
+        Self::For,
+        Self::In,
+        Self::While,
+        Self::Break,
+        Self::Continue,
+        Self::Return,
+        Self::Import,
+        Self::Include,
+        Self::As,
+        Self::Code,
+        Self::Ident,
+        Self::Bool,
+        Self::Int,
+        Self::Float,
+        Self::Numeric,
+        Self::Str,
+        Self::StrDelim,
+        Self::StrPart,
+        Self::Interpolation,
+        Self::CodeBlock,
+        Self::ContentBlock,
+        Self::Parenthesized,
+        Self::Array,
+        Self::Dict,
+        Self::Named,
+        Self::Keyed,
+        Self::Unary,
+        Self::Binary,
+        Self::FieldAccess,
+        Self::FuncCall,
+        Self::Args,
+        Self::Spread,
+        Self::Closure,
+        Self::Params,
+        Self::LetBinding,
+        Self::SetRule,
+        Self::ShowRule,
+        Self::Contextual,
+        Self::Conditional,
+        Self::WhileLoop,
+        Self::ForLoop,
+        Self::ModuleImport,
+        Self::ImportItems,
+        Self::RenamedImportItem,
+        Self::ModuleInclude,
+        Self::LoopBreak,
+        Self::LoopContinue,
+        Self::FuncReturn,
+        Self::Destructuring,
+        Self::DestructAssignment,
+        Self::LineComment,
+        Self::BlockComment,
+        Self::Error,
+        Self::Eof,
+    ];
+
+    /// Whether a node of this kind can have a direct child of the given kind.
+    ///
+    /// Errors can appear in any inner node. This describes the trees the
+    /// parser produces and is useful for validating trees from elsewhere, for
+    /// example deserialized ones.
+    pub fn can_contain(self, child: Self) -> bool {
+        let children = grammar::children(self);
+        children.contains(child) || (child == Self::Error && !children.is_empty())
+    }
+
+    /// The kinds of the direct children that a node of this kind can have,
+    /// in declaration order. Leaves have none.
+    pub fn children(self) -> impl Iterator<Item = Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(move |&child| self.can_contain(child))
+    }
+
     /// Is this a bracket, brace, or parenthesis?
     pub fn is_grouping(self) -> bool {
         matches!(
@@ -364,32 +572,48 @@ impl SyntaxKind {
     /// A human-readable name for the kind.
     pub fn name(self) -> &'static str {
         match self {
+            Self::Eof => "end of file",
+            Self::Error => "syntax error",
+            Self::LineComment => "line comment",
+            Self::BlockComment => "block comment",
             Self::Markup => "markup",
             Self::Text => "text",
             Self::Space => "space",
             Self::Linebreak => "line break",
+            Self::Rule => "horizontal rule",
             Self::Parbreak => "paragraph break",
             Self::Escape => "escape sequence",
             Self::Shorthand => "shorthand",
+            Self::Shortcode => "shortcode",
             Self::SmartQuote => "smart quote",
             Self::Strong => "strong content",
             Self::Emph => "emphasized content",
+            Self::Strike => "struck-through content",
             Self::Raw => "raw block",
             Self::RawLang => "raw language tag",
+            Self::RawMeta => "raw options",
             Self::RawTrimmed => "raw trimmed",
             Self::RawDelim => "raw delimiter",
             Self::Link => "link",
             Self::Label => "label",
             Self::Ref => "reference",
             Self::RefMarker => "reference marker",
+            Self::Footnote => "footnote",
+            Self::FootnoteMarker => "footnote marker",
             Self::Heading => "heading",
             Self::HeadingMarker => "heading marker",
             Self::ListItem => "list item",
             Self::ListMarker => "list marker",
+            Self::ListCheckbox => "list checkbox",
             Self::EnumItem => "enum item",
             Self::EnumMarker => "enum marker",
             Self::TermItem => "term list item",
             Self::TermMarker => "term marker",
+            Self::Table => "table",
+            Self::TableRow => "table row",
+            Self::TableCell => "table cell",
+            Self::TableDelim => "table delimiter",
+            Self::TableSeparator => "table separator",
             Self::Equation => "equation",
             Self::Math => "math",
             Self::MathIdent => "math identifier",
@@ -411,6 +635,7 @@ impl SyntaxKind {
             Self::Colon => "colon",
             Self::Star => "star",
             Self::Underscore => "underscore",
+            Self::StrikeDelim => "strike delimiter",
             Self::Dollar => "dollar sign",
             Self::Plus => "plus",
             Self::Minus => "minus",
@@ -459,6 +684,9 @@ impl SyntaxKind {
             Self::Float => "float",
             Self::Numeric => "numeric value",
             Self::Str => "string",
+            Self::StrDelim => "string delimiter",
+            Self::StrPart => "string part",
+            Self::Interpolation => "interpolated string",
             Self::CodeBlock => "code block",
             Self::ContentBlock => "content block",
             Self::Parenthesized => "group",
@@ -490,10 +718,6 @@ impl SyntaxKind {
             Self::FuncReturn => "`return` expression",
             Self::Destructuring => "destructuring pattern",
             Self::DestructAssignment => "destructuring assignment expression",
-            Self::LineComment => "line comment",
-            Self::BlockComment => "block comment",
-            Self::Error => "syntax error",
-            Self::Eof => "end of file",
         }
     }
 }
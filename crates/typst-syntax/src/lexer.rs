@@ -16,6 +16,8 @@ pub(super) struct Lexer<'s> {
     mode: LexMode,
     /// Whether the last token contained a newline.
     newline: bool,
+    /// Whether pipes separate table cells.
+    table: bool,
     /// The state held by raw line lexing.
     raw: Vec<(SyntaxKind, usize)>,
     /// An error for the last token.
@@ -33,6 +35,8 @@ pub(super) enum LexMode {
     Code,
     /// The contents of a raw block.
     Raw,
+    /// The literal pieces of an interpolated string.
+    Str,
 }
 
 impl<'s> Lexer<'s> {
@@ -43,6 +47,7 @@ impl<'s> Lexer<'s> {
             s: Scanner::new(text),
             mode,
             newline: false,
+            table: false,
             error: None,
             raw: Vec::new(),
         }
@@ -74,6 +79,16 @@ impl<'s> Lexer<'s> {
         self.newline
     }
 
+    /// Whether pipes separate table cells.
+    pub fn table(&self) -> bool {
+        self.table
+    }
+
+    /// Set whether pipes separate table cells.
+    pub fn set_table(&mut self, table: bool) {
+        self.table = table;
+    }
+
     /// Take out the last error, if any.
     pub fn take_error(&mut self) -> Option<EcoString> {
         self.error.take()
@@ -101,6 +116,10 @@ impl Lexer<'_> {
 
         self.newline = false;
         self.error = None;
+        if self.mode == LexMode::Str {
+            return self.str_part();
+        }
+
         let start = self.s.cursor();
         match self.s.eat() {
             Some(c) if is_space(c, self.mode) => self.whitespace(start, c),
@@ -114,7 +133,7 @@ impl Lexer<'_> {
                 LexMode::Markup => self.markup(start, c),
                 LexMode::Math => self.math(start, c),
                 LexMode::Code => self.code(start, c),
-                LexMode::Raw => unreachable!(),
+                LexMode::Raw | LexMode::Str => unreachable!(),
             },
 
             None => SyntaxKind::Eof,
@@ -177,7 +196,12 @@ impl Lexer<'_> {
             'h' if self.s.eat_if("ttps://") => self.link(),
             '<' if self.s.at(is_id_continue) => self.label(),
             '@' => self.ref_marker(),
+            '^' if self.s.at('[') => SyntaxKind::FootnoteMarker,
 
+            '-' if self.at_rule(start) => {
+                self.s.eat_while('-');
+                SyntaxKind::Rule
+            }
             '.' if self.s.eat_if("..") => SyntaxKind::Shorthand,
             '-' if self.s.eat_if("--") => SyntaxKind::Shorthand,
             '-' if self.s.eat_if('-') => SyntaxKind::Shorthand,
@@ -192,7 +216,12 @@ impl Lexer<'_> {
             '\'' => SyntaxKind::SmartQuote,
             '"' => SyntaxKind::SmartQuote,
             '$' => SyntaxKind::Dollar,
+            '~' if self.at_strike_delim() => {
+                self.s.eat();
+                SyntaxKind::StrikeDelim
+            }
             '~' => SyntaxKind::Shorthand,
+            ':' if self.shortcode() => SyntaxKind::Shortcode,
             ':' => SyntaxKind::Colon,
             '=' => {
                 self.s.eat_while('=');
@@ -205,6 +234,11 @@ impl Lexer<'_> {
             '-' if self.space_or_end() => SyntaxKind::ListMarker,
             '+' if self.space_or_end() => SyntaxKind::EnumMarker,
             '/' if self.space_or_end() => SyntaxKind::TermMarker,
+            '|' if self.table && self.at_table_separator(start) => {
+                self.s.eat_until(is_newline);
+                SyntaxKind::TableSeparator
+            }
+            '|' if self.table || self.at_table_header() => SyntaxKind::TableDelim,
             '0'..='9' => self.numbering(start),
 
             _ => self.text(),
@@ -213,17 +247,16 @@ impl Lexer<'_> {
 
     fn backslash(&mut self) -> SyntaxKind {
         if self.s.eat_if("u{") {
-            let hex = self.s.eat_while(char::is_ascii_alphanumeric);
+            let sequence = self.s.eat_while(is_unicode_escape);
             if !self.s.eat_if('}') {
                 return self.error("unclosed Unicode escape sequence");
             }
 
-            if u32::from_str_radix(hex, 16)
-                .ok()
-                .and_then(std::char::from_u32)
-                .is_none()
-            {
-                return self.error(eco_format!("invalid Unicode codepoint: {}", hex));
+            if unicode_escape(sequence).is_none() {
+                return self.error(match sequence.strip_prefix("name:") {
+                    Some(name) => eco_format!("unknown Unicode character name: {}", name),
+                    None => eco_format!("invalid Unicode codepoint: {}", sequence),
+                });
             }
 
             return SyntaxKind::Escape;
@@ -295,6 +328,7 @@ impl Lexer<'_> {
         if self.s.eat_if(is_id_start) {
             self.s.eat_while(is_id_continue);
             self.push_raw(SyntaxKind::RawLang);
+            self.raw_meta();
         }
 
         // Determine inner content between backticks and with trimmed
@@ -353,6 +387,48 @@ impl Lexer<'_> {
         self.s.jump(end);
     }
 
+    /// Lexes the options directly following a language tag: a range of lines
+    /// to display and a list of lines to highlight, e.g. `:3..10{5,7..8}`.
+    fn raw_meta(&mut self) {
+        fn number(s: &mut Scanner) -> bool {
+            !s.eat_while(|c: char| c.is_ascii_digit()).is_empty()
+        }
+
+        fn range(s: &mut Scanner, single: bool) -> bool {
+            number(s) && if s.eat_if("..") { number(s) } else { single }
+        }
+
+        let mut s = self.s;
+        let mut found = false;
+
+        if s.eat_if(':') {
+            if !range(&mut s, false) {
+                return;
+            }
+            found = true;
+        }
+
+        if s.eat_if('{') {
+            loop {
+                if !range(&mut s, true) {
+                    return;
+                }
+                if !s.eat_if(',') {
+                    break;
+                }
+            }
+            if !s.eat_if('}') {
+                return;
+            }
+            found = true;
+        }
+
+        if found {
+            self.s = s;
+            self.push_raw(SyntaxKind::RawMeta);
+        }
+    }
+
     fn push_raw(&mut self, kind: SyntaxKind) {
         let end = self.s.cursor();
         self.raw.push((kind, end));
@@ -421,7 +497,7 @@ impl Lexer<'_> {
         table! {
             | ' ' | '\t' | '\n' | '\x0b' | '\x0c' | '\r' | '\\' | '/'
             | '[' | ']' | '~' | '-' | '.' | '\'' | '"' | '*' | '_'
-            | ':' | 'h' | '`' | '$' | '<' | '>' | '@' | '#'
+            | ':' | 'h' | '`' | '$' | '<' | '>' | '@' | '#' | '|' | '^'
         };
 
         loop {
@@ -439,6 +515,8 @@ impl Lexer<'_> {
                 Some('.') if !s.at("..") => {}
                 Some('h') if !s.at("ttp://") && !s.at("ttps://") => {}
                 Some('@') if !s.at(is_id_start) => {}
+                Some('^') if !s.at('[') => {}
+                Some('|') if !self.table => {}
                 _ => break,
             }
 
@@ -466,6 +544,76 @@ impl Lexer<'_> {
         wordy(prev) && wordy(next)
     }
 
+    /// Whether the `-` that was just eaten starts a horizontal rule: three or
+    /// more dashes that are alone on their line.
+    fn at_rule(&self, start: usize) -> bool {
+        let line = self.s.get(0..start).trim_end_matches([' ', '\t']);
+        if !line.is_empty() && !line.ends_with(is_newline) {
+            return false;
+        }
+
+        let after = self.s.after();
+        let rest = after.trim_start_matches('-');
+        after.len() - rest.len() >= 2
+            && rest
+                .chars()
+                .take_while(|&c| !is_newline(c))
+                .all(|c| c == ' ' || c == '\t')
+    }
+
+    /// Tries to eat the rest of an emoji shortcode like `:face.grin:` after
+    /// its opening colon.
+    fn shortcode(&mut self) -> bool {
+        if self.s.scout(-2).is_some_and(char::is_alphanumeric) {
+            return false;
+        }
+
+        let mut s = self.s;
+        if !s.eat_if(is_id_start) {
+            return false;
+        }
+
+        s.eat_while(|c| is_id_continue(c) || c == '.');
+        if s.before().ends_with('.')
+            || !s.eat_if(':')
+            || s.peek().is_some_and(char::is_alphanumeric)
+        {
+            return false;
+        }
+
+        self.s = s;
+        true
+    }
+
+    /// Whether the `~` that was just eaten starts a strike delimiter. Longer
+    /// runs of tildes are kept as non-breaking spaces.
+    fn at_strike_delim(&self) -> bool {
+        self.s.scout(-2) != Some('~') && self.s.at('~') && self.s.scout(1) != Some('~')
+    }
+
+    /// Whether the `|` that was just eaten starts the header row of a table:
+    /// a row that is followed by a separator line like `|---|:-:|`.
+    fn at_table_header(&self) -> bool {
+        let mut s = Scanner::new(self.s.after());
+        let line = s.eat_until(is_newline);
+        if !line.trim_end().ends_with('|') || !s.eat_newline() {
+            return false;
+        }
+        s.eat_while([' ', '\t']);
+        s.eat_if('|') && is_table_separator(s.eat_until(is_newline))
+    }
+
+    /// Whether the `|` that was just eaten starts a table separator line.
+    fn at_table_separator(&self, start: usize) -> bool {
+        let line = self.s.get(0..start).trim_end_matches([' ', '\t']);
+        if !line.is_empty() && !line.ends_with(is_newline) {
+            return false;
+        }
+
+        let mut s = Scanner::new(self.s.after());
+        is_table_separator(s.eat_until(is_newline))
+    }
+
     fn space_or_end(&self) -> bool {
         self.s.done() || self.s.at(char::is_whitespace)
     }
@@ -565,6 +713,14 @@ impl Lexer<'_> {
             '0'..='9' => self.number(start, c),
             '.' if self.s.at(char::is_ascii_digit) => self.number(start, c),
             '"' => self.string(),
+            'r' if self.at_string_prefix() => {
+                self.s.eat();
+                self.raw_string()
+            }
+            'f' if self.at_string_prefix() => {
+                self.s.eat();
+                SyntaxKind::StrDelim
+            }
 
             '=' if self.s.eat_if('=') => SyntaxKind::EqEq,
             '!' if self.s.eat_if('=') => SyntaxKind::ExclEq,
@@ -710,6 +866,57 @@ impl Lexer<'_> {
 
         SyntaxKind::Str
     }
+
+    /// Lexes the pieces of an interpolated string after its opening `f"`.
+    fn str_part(&mut self) -> SyntaxKind {
+        let start = self.s.cursor();
+        match self.s.eat() {
+            Some('"') => SyntaxKind::StrDelim,
+            Some('{') => SyntaxKind::LeftBrace,
+            Some(_) => {
+                self.s.jump(start);
+                self.str_content();
+                SyntaxKind::StrPart
+            }
+            None => SyntaxKind::Eof,
+        }
+    }
+
+    /// Eat string content up to the closing quote or the brace opening an
+    /// embedded expression.
+    fn str_content(&mut self) {
+        loop {
+            self.s.eat_until(|c| c == '"' || c == '\\' || c == '{');
+            if !self.s.eat_if('\\') {
+                break;
+            }
+
+            if self.s.eat_if("u{") {
+                self.s.eat_while(is_unicode_escape);
+                self.s.eat_if('}');
+            } else {
+                self.s.eat();
+            }
+        }
+    }
+
+    /// Whether the `r` or `f` that was just eaten prefixes a string.
+    ///
+    /// Directly after a hash or a dot, it is always an identifier, so that
+    /// `#r"..."` in markup still embeds the variable `r` followed by a quote.
+    fn at_string_prefix(&self) -> bool {
+        self.s.at('"') && !matches!(self.s.scout(-2), Some('#' | '.'))
+    }
+
+    fn raw_string(&mut self) -> SyntaxKind {
+        self.s.eat_until('"');
+
+        if !self.s.eat_if('"') {
+            return self.error("unclosed string");
+        }
+
+        SyntaxKind::Str
+    }
 }
 
 /// Try to parse an identifier into a keyword.
@@ -769,6 +976,38 @@ fn is_space(character: char, mode: LexMode) -> bool {
     }
 }
 
+/// Whether a character can be part of a Unicode escape sequence, which holds
+/// either a hexadecimal codepoint or a character name.
+#[inline]
+pub(crate) fn is_unicode_escape(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | ':')
+}
+
+/// Resolves the contents of a Unicode escape sequence: Either a hexadecimal
+/// codepoint like `1F600` or a character name with a `name:` prefix like
+/// `name:EM DASH`.
+///
+/// The prefix is required because many names, like `BED`, are also valid
+/// hexadecimal codepoints.
+pub(crate) fn unicode_escape(sequence: &str) -> Option<char> {
+    match sequence.strip_prefix("name:") {
+        Some(name) => unicode_names2::character(&name.to_ascii_uppercase()),
+        None => u32::from_str_radix(sequence, 16).ok().and_then(std::char::from_u32),
+    }
+}
+
+/// Whether the rest of a line after its opening pipe separates the header of a
+/// table from its body: `---|:-:|`.
+fn is_table_separator(line: &str) -> bool {
+    let Some(inner) = line.trim_end().strip_suffix('|') else { return false };
+    inner.split('|').all(|cell| {
+        let cell = cell.trim_matches([' ', '\t']);
+        let dashes = cell.strip_prefix(':').unwrap_or(cell);
+        let dashes = dashes.strip_suffix(':').unwrap_or(dashes);
+        !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+    })
+}
+
 /// Whether a character is interpreted as a newline by Typst.
 #[inline]
 pub fn is_newline(character: char) -> bool {
@@ -1,29 +1,39 @@
 //! Parser and syntax tree for Typst.
 
 pub mod ast;
+pub mod lint;
 pub mod package;
 
+mod chunks;
+mod diff;
 mod file;
+mod format;
+mod grammar;
 mod highlight;
 mod kind;
 mod lexer;
 mod node;
 mod parser;
 mod path;
+mod query;
 mod reparser;
 mod set;
 mod source;
 mod span;
 
+pub use self::chunks::{parse_chunks, Chunk, Chunks};
+pub use self::diff::{diff, Change};
 pub use self::file::FileId;
+pub use self::format::{format, FormatConfig};
 pub use self::highlight::{highlight, highlight_html, Tag};
 pub use self::kind::SyntaxKind;
 pub use self::lexer::{
     is_id_continue, is_id_start, is_ident, is_newline, link_prefix, split_newlines,
 };
-pub use self::node::{LinkedChildren, LinkedNode, Side, SyntaxError, SyntaxNode};
+pub use self::node::{LinkedChildren, LinkedNode, Side, SyntaxError, SyntaxNode, Tokens};
 pub use self::parser::{parse, parse_code, parse_math};
 pub use self::path::VirtualPath;
+pub use self::query::{Match, Pattern};
 pub use self::source::Source;
 pub use self::span::{Span, Spanned};
 
@@ -0,0 +1,234 @@
+//! Purely syntactic checks for common mistakes.
+//!
+//! A [`Linter`] runs a list of [rules](Rule) over a syntax tree and collects
+//! the [lints](Lint) they report. It comes with a few built-in rules, and
+//! downstream crates can register their own.
+
+use std::fmt::Debug;
+
+use ecow::{eco_format, EcoString, EcoVec};
+
+use crate::ast::{self, AstNode};
+use crate::{LinkedNode, Span, SyntaxKind, SyntaxNode};
+
+/// A problem reported by a lint rule.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Lint {
+    /// The name of the rule that reported the problem.
+    pub rule: &'static str,
+    /// The location of the problem.
+    pub span: Span,
+    /// A short message describing the problem.
+    pub message: EcoString,
+    /// Additional hints to the user, indicating how this problem could be
+    /// avoided or worked around.
+    pub hints: EcoVec<EcoString>,
+}
+
+/// A check over a syntax tree.
+pub trait Rule: Debug + Send + Sync {
+    /// The name of the rule, in kebab-case.
+    fn name(&self) -> &'static str;
+
+    /// Check a tree and report problems to the sink.
+    fn check(&self, root: &LinkedNode, sink: &mut Sink);
+}
+
+/// Collects the lints of one rule.
+pub struct Sink {
+    rule: &'static str,
+    lints: Vec<Lint>,
+}
+
+impl Sink {
+    /// Report a problem at a span.
+    pub fn report(&mut self, span: Span, message: impl Into<EcoString>) -> &mut Lint {
+        self.lints.push(Lint {
+            rule: self.rule,
+            span,
+            message: message.into(),
+            hints: EcoVec::new(),
+        });
+        self.lints.last_mut().unwrap()
+    }
+}
+
+impl Lint {
+    /// Add a user-presentable hint.
+    pub fn hint(&mut self, hint: impl Into<EcoString>) -> &mut Self {
+        self.hints.push(hint.into());
+        self
+    }
+}
+
+/// Runs a set of rules over syntax trees.
+#[derive(Debug)]
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Linter {
+    /// Create a linter without any rules.
+    pub fn new() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Register a rule.
+    pub fn add(&mut self, rule: impl Rule + 'static) -> &mut Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// The registered rules.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Run all rules over a tree and return their lints, ordered by rule.
+    pub fn check(&self, root: &SyntaxNode) -> Vec<Lint> {
+        let root = LinkedNode::new(root);
+        let mut lints = vec![];
+        for rule in &self.rules {
+            let mut sink = Sink { rule: rule.name(), lints: vec![] };
+            rule.check(&root, &mut sink);
+            lints.extend(sink.lints);
+        }
+        lints
+    }
+}
+
+impl Default for Linter {
+    /// Create a linter with all built-in rules.
+    fn default() -> Self {
+        let mut linter = Self::new();
+        linter.add(HeadingJump).add(EmptyEmphasis);
+        linter
+    }
+}
+
+/// Reports headings that are more than one level deeper than the heading
+/// before them: `= A` followed by `=== B`.
+#[derive(Debug, Copy, Clone)]
+pub struct HeadingJump;
+
+impl Rule for HeadingJump {
+    fn name(&self) -> &'static str {
+        "heading-jump"
+    }
+
+    fn check(&self, root: &LinkedNode, sink: &mut Sink) {
+        let mut prev = None;
+        visit(root, &mut |node| {
+            let Some(heading) = node.cast::<ast::Heading>() else { return };
+            let depth = heading.depth().get();
+            if let Some(prev) = prev.filter(|&prev| depth > prev + 1) {
+                sink.report(
+                    node.span(),
+                    eco_format!("heading level jumps from {prev} to {depth}"),
+                )
+                .hint(eco_format!("consider using a level {} heading", prev + 1));
+            }
+            prev = Some(depth);
+        });
+    }
+}
+
+/// Reports strong and regular emphasis without a body: `__`.
+#[derive(Debug, Copy, Clone)]
+pub struct EmptyEmphasis;
+
+impl Rule for EmptyEmphasis {
+    fn name(&self) -> &'static str {
+        "empty-emphasis"
+    }
+
+    fn check(&self, root: &LinkedNode, sink: &mut Sink) {
+        visit(root, &mut |node| {
+            let (body, message) = match node.kind() {
+                SyntaxKind::Strong => {
+                    (node.cast::<ast::Strong>().unwrap().body(), "strong emphasis")
+                }
+                SyntaxKind::Emph => {
+                    (node.cast::<ast::Emph>().unwrap().body(), "emphasis")
+                }
+                _ => return,
+            };
+            if body.exprs().all(|expr| expr.to_untyped().kind().is_trivia()) {
+                sink.report(node.span(), eco_format!("{message} is empty"))
+                    .hint("remove it or add content");
+            }
+        });
+    }
+}
+
+/// Call `f` for a node and all its descendants, in source order.
+fn visit<'a>(node: &LinkedNode<'a>, f: &mut impl FnMut(&LinkedNode<'a>)) {
+    f(node);
+    for child in node.children() {
+        visit(&child, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Source;
+
+    #[track_caller]
+    fn test(text: &str, expected: &[(&str, &str)]) {
+        let source = Source::detached(text);
+        let lints = Linter::default().check(source.root());
+        let found: Vec<_> = lints
+            .iter()
+            .map(|lint| (lint.rule, &text[source.range(lint.span).unwrap()]))
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_lint_heading_jump() {
+        test("= A\n== B\n= C\n=== D\n==== E", &[("heading-jump", "=== D")]);
+        test("== A\n=== B", &[]);
+    }
+
+    #[test]
+    fn test_lint_empty_emphasis() {
+        test(
+            "a __ *b* ** _ _",
+            &[
+                ("empty-emphasis", "__"),
+                ("empty-emphasis", "**"),
+                ("empty-emphasis", "_ _"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_lint_custom_rule() {
+        #[derive(Debug)]
+        struct NoTodo;
+
+        impl Rule for NoTodo {
+            fn name(&self) -> &'static str {
+                "no-todo"
+            }
+
+            fn check(&self, root: &LinkedNode, sink: &mut Sink) {
+                visit(root, &mut |node| {
+                    if node.kind() == SyntaxKind::Text && node.text().contains("TODO") {
+                        sink.report(node.span(), "unresolved todo");
+                    }
+                });
+            }
+        }
+
+        let source = Source::detached("= A\n=== B TODO");
+        let mut linter = Linter::default();
+        linter.add(NoTodo);
+        let lints = linter.check(source.root());
+        let rules: Vec<_> = lints.iter().map(|lint| lint.rule).collect();
+        assert_eq!(rules, ["heading-jump", "no-todo"]);
+        assert_eq!(lints[0].message, "heading level jumps from 1 to 3");
+        assert_eq!(lints[0].hints.as_slice(), ["consider using a level 2 heading"]);
+    }
+}
@@ -4,9 +4,12 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use ecow::{eco_format, eco_vec, EcoString, EcoVec};
+use serde::de::Error as _;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::ast::AstNode;
-use crate::{FileId, Span, SyntaxKind};
+use crate::ast::{AstNode, WithTrivia};
+use crate::{is_newline, FileId, Span, SyntaxKind};
 
 /// A node in the untyped syntax tree.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -104,6 +107,20 @@ impl SyntaxNode {
         }
     }
 
+    /// An iterator over the tokens of the node, that is, its leaves in source
+    /// order, which yields the kind, span, and byte range of each token.
+    ///
+    /// The parser decides which parts of a file are lexed as markup, math or
+    /// code, so exact token kinds are only available from a parsed tree. For
+    /// a file, take them from [`Source::root`](crate::Source::root) to get
+    /// numbered spans and to keep them up to date incrementally.
+    pub fn tokens(&self) -> Tokens<'_> {
+        Tokens {
+            stack: vec![std::slice::from_ref(self).iter()],
+            offset: 0,
+        }
+    }
+
     /// Whether the node can be cast to the given AST node.
     pub fn is<'a, T: AstNode<'a>>(&'a self) -> bool {
         self.cast::<T>().is_some()
@@ -174,6 +191,45 @@ impl SyntaxNode {
             _ => false,
         }
     }
+
+    /// Rebuild the tree, replacing each node for which `f` returns a
+    /// replacement.
+    ///
+    /// Nodes are visited top-down and the children of a replaced node are not
+    /// visited. Everything else, including spaces and comments, is kept as is,
+    /// so the [text](Self::into_text) of the result only differs in the
+    /// replaced nodes. To insert nodes, replace their parent with a new
+    /// [inner](Self::inner) node.
+    ///
+    /// Rebuilt nodes have detached spans. To continue working with the result
+    /// as a file, create a new [`Source`](crate::Source) from its text.
+    pub fn rewrite(&self, mut f: impl FnMut(&SyntaxNode) -> Option<SyntaxNode>) -> Self {
+        self.rewritten(&mut f).unwrap_or_else(|| self.clone())
+    }
+
+    /// The rebuilt node if it or any of its descendants was replaced.
+    fn rewritten<F>(&self, f: &mut F) -> Option<Self>
+    where
+        F: FnMut(&SyntaxNode) -> Option<SyntaxNode>,
+    {
+        if let Some(replacement) = f(self) {
+            return Some(replacement);
+        }
+
+        let Repr::Inner(inner) = &self.0 else { return None };
+        let mut children: Option<Vec<SyntaxNode>> = None;
+        for (i, child) in inner.children.iter().enumerate() {
+            match (child.rewritten(f), &mut children) {
+                (Some(new), children) => {
+                    children.get_or_insert_with(|| inner.children[..i].to_vec()).push(new)
+                }
+                (None, Some(children)) => children.push(child.clone()),
+                (None, None) => {}
+            }
+        }
+
+        children.map(|children| Self::inner(inner.kind, children))
+    }
 }
 
 impl SyntaxNode {
@@ -326,6 +382,142 @@ impl Default for SyntaxNode {
     }
 }
 
+/// Serializes leaf nodes as `{kind, text}`, inner nodes as `{kind, children}`
+/// and error nodes as `{kind, text, message, hints}`. Spans are not part of
+/// the serialized form, so deserialized nodes are detached.
+impl Serialize for SyntaxNode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Repr::Leaf(leaf) => {
+                let mut s = serializer.serialize_struct("SyntaxNode", 2)?;
+                s.serialize_field("kind", &leaf.kind)?;
+                s.serialize_field("text", &leaf.text)?;
+                s.end()
+            }
+            Repr::Inner(inner) => {
+                let mut s = serializer.serialize_struct("SyntaxNode", 2)?;
+                s.serialize_field("kind", &inner.kind)?;
+                s.serialize_field("children", &inner.children)?;
+                s.end()
+            }
+            Repr::Error(node) => {
+                let mut s = serializer.serialize_struct("SyntaxNode", 4)?;
+                s.serialize_field("kind", &SyntaxKind::Error)?;
+                s.serialize_field("text", &node.text)?;
+                s.serialize_field("message", &node.error.message)?;
+                s.serialize_field("hints", &node.error.hints)?;
+                s.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SyntaxNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Node {
+            kind: SyntaxKind,
+            text: Option<EcoString>,
+            children: Option<Vec<SyntaxNode>>,
+            #[serde(default)]
+            message: EcoString,
+            #[serde(default)]
+            hints: Vec<EcoString>,
+        }
+
+        let node = Node::deserialize(deserializer)?;
+        let name = node.kind.name();
+        Ok(match (node.text, node.children) {
+            (None, Some(children)) if is_inner(node.kind) => {
+                Self::inner(node.kind, children)
+            }
+            (text, None) if node.kind == SyntaxKind::Error => {
+                let mut error = Self::error(node.message, text.unwrap_or_default());
+                for hint in node.hints {
+                    error.hint(hint);
+                }
+                error
+            }
+            (text, None) if !is_inner(node.kind) => {
+                Self::leaf(node.kind, text.unwrap_or_default())
+            }
+            (Some(_), Some(_)) => {
+                return Err(D::Error::custom(format!(
+                    "{name} node cannot have both text and children"
+                )))
+            }
+            (_, Some(_)) => {
+                return Err(D::Error::custom(format!("{name} node cannot have children")))
+            }
+            (_, None) => {
+                return Err(D::Error::custom(format!("{name} node must have children")))
+            }
+        })
+    }
+}
+
+/// Whether nodes of this kind are inner nodes with children rather than
+/// leaves with text.
+fn is_inner(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Markup
+            | SyntaxKind::Strong
+            | SyntaxKind::Emph
+            | SyntaxKind::Strike
+            | SyntaxKind::Raw
+            | SyntaxKind::Ref
+            | SyntaxKind::Footnote
+            | SyntaxKind::Heading
+            | SyntaxKind::ListItem
+            | SyntaxKind::EnumItem
+            | SyntaxKind::TermItem
+            | SyntaxKind::Table
+            | SyntaxKind::TableRow
+            | SyntaxKind::TableCell
+            | SyntaxKind::Equation
+            | SyntaxKind::Math
+            | SyntaxKind::MathDelimited
+            | SyntaxKind::MathAttach
+            | SyntaxKind::MathPrimes
+            | SyntaxKind::MathFrac
+            | SyntaxKind::MathRoot
+            | SyntaxKind::Code
+            | SyntaxKind::Interpolation
+            | SyntaxKind::CodeBlock
+            | SyntaxKind::ContentBlock
+            | SyntaxKind::Parenthesized
+            | SyntaxKind::Array
+            | SyntaxKind::Dict
+            | SyntaxKind::Named
+            | SyntaxKind::Keyed
+            | SyntaxKind::Unary
+            | SyntaxKind::Binary
+            | SyntaxKind::FieldAccess
+            | SyntaxKind::FuncCall
+            | SyntaxKind::Args
+            | SyntaxKind::Spread
+            | SyntaxKind::Closure
+            | SyntaxKind::Params
+            | SyntaxKind::LetBinding
+            | SyntaxKind::SetRule
+            | SyntaxKind::ShowRule
+            | SyntaxKind::Contextual
+            | SyntaxKind::Conditional
+            | SyntaxKind::WhileLoop
+            | SyntaxKind::ForLoop
+            | SyntaxKind::ModuleImport
+            | SyntaxKind::ImportItems
+            | SyntaxKind::RenamedImportItem
+            | SyntaxKind::ModuleInclude
+            | SyntaxKind::LoopBreak
+            | SyntaxKind::LoopContinue
+            | SyntaxKind::FuncReturn
+            | SyntaxKind::Destructuring
+            | SyntaxKind::DestructAssignment
+    )
+}
+
 /// A leaf node in the untyped syntax tree.
 #[derive(Clone, Eq, PartialEq, Hash)]
 struct LeafNode {
@@ -693,7 +885,13 @@ pub struct LinkedNode<'a> {
 impl<'a> LinkedNode<'a> {
     /// Start a new traversal at a root node.
     pub fn new(root: &'a SyntaxNode) -> Self {
-        Self { node: root, parent: None, index: 0, offset: 0 }
+        Self::with_offset(root, 0)
+    }
+
+    /// Start a new traversal at a root node that starts at the given byte
+    /// offset in the file.
+    pub fn with_offset(root: &'a SyntaxNode, offset: usize) -> Self {
+        Self { node: root, parent: None, index: 0, offset }
     }
 
     /// Get the contained syntax node.
@@ -767,6 +965,12 @@ impl<'a> LinkedNode<'a> {
         self.parent.as_deref()
     }
 
+    /// Get an iterator over this node's ancestors, from its parent up to the
+    /// root.
+    pub fn ancestors(&self) -> impl Iterator<Item = &Self> {
+        std::iter::successors(self.parent(), |node| node.parent())
+    }
+
     /// Get the first previous non-trivia sibling node.
     pub fn prev_sibling(&self) -> Option<Self> {
         let parent = self.parent()?;
@@ -795,6 +999,59 @@ impl<'a> LinkedNode<'a> {
         }
     }
 
+    /// Get the trivia directly before this node, in source order.
+    ///
+    /// A hash that introduces an embedded expression is skipped, so that the
+    /// comments above `[#let x = 1]` belong to the let binding. If the node
+    /// starts its parent, the trivia before the parent is included, too.
+    pub fn leading_trivia(&self) -> Vec<Self> {
+        let Some(parent) = self.parent() else { return vec![] };
+        let mut before: Vec<_> = parent.children().take(self.index).collect();
+        if before.last().is_some_and(|prev| prev.kind() == SyntaxKind::Hash) {
+            before.pop();
+        }
+        match before.iter().rposition(|prev| !prev.kind().is_trivia()) {
+            Some(i) => before.split_off(i + 1),
+            None => {
+                let mut trivia = parent.leading_trivia();
+                trivia.extend(before);
+                trivia
+            }
+        }
+    }
+
+    /// Get the trivia directly after this node up to the end of its line, in
+    /// source order.
+    ///
+    /// If the node ends its parent, the trivia after the parent is included,
+    /// too.
+    pub fn trailing_trivia(&self) -> Vec<Self> {
+        let Some(parent) = self.parent() else { return vec![] };
+        let mut trivia = vec![];
+        for next in parent.children().skip(self.index + 1) {
+            let kind = next.kind();
+            if !kind.is_trivia()
+                || (matches!(kind, SyntaxKind::Space | SyntaxKind::Parbreak)
+                    && next.text().chars().any(is_newline))
+            {
+                return trivia;
+            }
+            trivia.push(next);
+        }
+        trivia.extend(parent.trailing_trivia());
+        trivia
+    }
+
+    /// Cast this node to a typed node together with its leading and trailing
+    /// trivia.
+    pub fn cast_with_trivia<T: AstNode<'a>>(&self) -> Option<WithTrivia<'a, T>> {
+        Some(WithTrivia {
+            node: self.get().cast()?,
+            leading: self.leading_trivia(),
+            trailing: self.trailing_trivia(),
+        })
+    }
+
     /// Get the kind of this node's parent.
     pub fn parent_kind(&self) -> Option<SyntaxKind> {
         Some(self.parent()?.node.kind())
@@ -983,6 +1240,38 @@ impl DoubleEndedIterator for LinkedChildren<'_> {
 
 impl ExactSizeIterator for LinkedChildren<'_> {}
 
+/// An iterator over the tokens of a syntax node.
+///
+/// This struct is created by [`SyntaxNode::tokens`].
+#[derive(Clone)]
+pub struct Tokens<'a> {
+    stack: Vec<std::slice::Iter<'a, SyntaxNode>>,
+    offset: usize,
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = (SyntaxKind, Span, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let iter = self.stack.last_mut()?;
+            let Some(node) = iter.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            if let Repr::Inner(inner) = &node.0 {
+                self.stack.push(inner.children.iter());
+                continue;
+            }
+
+            let start = self.offset;
+            self.offset += node.len();
+            return Some((node.kind(), node.span(), start..self.offset));
+        }
+    }
+}
+
 /// Result of numbering a node within an interval.
 pub(super) type NumberingResult = Result<(), Unnumberable>;
 
@@ -1001,7 +1290,7 @@ impl std::error::Error for Unnumberable {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Source;
+    use crate::{ast, Source};
 
     #[test]
     fn test_linked_node() {
@@ -1023,6 +1312,16 @@ mod tests {
         assert_eq!(prev.text(), "set");
     }
 
+    #[test]
+    fn test_linked_node_ancestors() {
+        let source = Source::detached("#set text(12pt, red)");
+        let leaf = LinkedNode::new(source.root()).leaf_at(11, Side::After).unwrap();
+        assert_eq!(leaf.text(), "12pt");
+
+        let kinds: Vec<_> = leaf.ancestors().map(|node| node.kind()).collect();
+        assert_eq!(kinds, [SyntaxKind::Args, SyntaxKind::SetRule, SyntaxKind::Markup]);
+    }
+
     #[test]
     fn test_linked_node_non_trivia_leaf() {
         let source = Source::detached("#set fun(12pt, red)");
@@ -1048,4 +1347,124 @@ mod tests {
         assert_eq!(prev.text(), "=");
         assert_eq!(leaf.text(), "10");
     }
+
+    #[test]
+    fn test_linked_node_trivia() {
+        let source =
+            Source::detached("// Doc.\n/* More. */ #let x = 1 // Tail.\n// Next.");
+        let root = LinkedNode::new(source.root());
+        let binding = root.children().find(|node| node.is::<ast::LetBinding>()).unwrap();
+        let texts = |nodes: Vec<LinkedNode>| {
+            nodes.iter().map(|node| node.text().clone()).collect::<Vec<_>>()
+        };
+
+        let with = binding.cast_with_trivia::<ast::LetBinding>().unwrap();
+        assert_eq!(with.node.to_untyped().clone().into_text(), "let x = 1");
+        assert_eq!(texts(with.leading), ["// Doc.", "\n", "/* More. */", " "]);
+        assert_eq!(texts(with.trailing), [" ", "// Tail."]);
+
+        let code = Source::detached("#{\n  let y = 2\n  y\n}");
+        let root = LinkedNode::new(code.root());
+        let leaf = root.leaf_at(10, Side::Before).unwrap();
+        assert_eq!(leaf.text(), "y");
+        let binding = leaf.parent().unwrap();
+        assert_eq!(binding.kind(), SyntaxKind::LetBinding);
+        assert_eq!(texts(binding.leading_trivia()), ["\n  "]);
+        assert!(binding.trailing_trivia().is_empty());
+
+        // The trivia around a block belongs to its first and last expression.
+        let inline = Source::detached("#{ /* A */ x /* B */ }");
+        let root = LinkedNode::new(inline.root());
+        let x = root.leaf_at(11, Side::After).unwrap();
+        assert_eq!(x.text(), "x");
+        assert_eq!(texts(x.leading_trivia()), [" ", "/* A */", " "]);
+        assert_eq!(texts(x.trailing_trivia()), [" ", "/* B */", " "]);
+    }
+
+    #[test]
+    fn test_tokens() {
+        use SyntaxKind::*;
+        let source = Source::detached("*a* #f(`x`) $xy$");
+        let tokens: Vec<_> = source
+            .root()
+            .tokens()
+            .map(|(kind, span, range)| {
+                assert_eq!(source.range(span), Some(range.clone()));
+                (kind, range)
+            })
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Star, 0..1),
+                (Text, 1..2),
+                (Star, 2..3),
+                (Space, 3..4),
+                (Hash, 4..5),
+                (Ident, 5..6),
+                (LeftParen, 6..7),
+                (RawDelim, 7..8),
+                (Text, 8..9),
+                (RawDelim, 9..10),
+                (RightParen, 10..11),
+                (Space, 11..12),
+                (Dollar, 12..13),
+                (MathIdent, 13..15),
+                (Dollar, 15..16),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let source = Source::detached("= A\n// one\n== B /* two */\n\n*C*");
+        let root = source.root();
+
+        // Bump all heading levels.
+        let bumped = root.rewrite(|node| {
+            (node.kind() == SyntaxKind::HeadingMarker)
+                .then(|| SyntaxNode::leaf(node.kind(), eco_format!("={}", node.text())))
+        });
+        assert_eq!(bumped.into_text(), "== A\n// one\n=== B /* two */\n\n*C*");
+
+        // Insert a label into each heading.
+        let labelled = root.rewrite(|node| {
+            (node.kind() == SyntaxKind::Heading).then(|| {
+                let mut children: Vec<_> = node.children().cloned().collect();
+                children.push(SyntaxNode::leaf(SyntaxKind::Space, " "));
+                children.push(SyntaxNode::leaf(SyntaxKind::Label, "<h>"));
+                SyntaxNode::inner(node.kind(), children)
+            })
+        });
+        assert_eq!(labelled.into_text(), "= A <h>\n// one\n== B /* two */ <h>\n\n*C*");
+
+        // Untouched trees are returned as they are.
+        let same = root.rewrite(|_| None);
+        assert_eq!(same.span(), root.span());
+        assert!(same.spanless_eq(root));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let root = crate::parse("= Heading *strong\n#let x = (1, \n$a^$ #f(..");
+        assert!(root.erroneous());
+
+        let json = serde_json::to_string(&root).unwrap();
+        let node: SyntaxNode = serde_json::from_str(&json).unwrap();
+        assert!(node.spanless_eq(&root));
+        assert_eq!(node.errors().len(), root.errors().len());
+        assert_eq!(node.into_text(), root.into_text());
+    }
+
+    #[test]
+    fn test_serde_rejects_malformed_nodes() {
+        let reject = |json| serde_json::from_str::<SyntaxNode>(json).is_err();
+        assert!(reject(r#"{"kind":"text","text":"a","children":[]}"#));
+        assert!(reject(r#"{"kind":"text","children":[]}"#));
+        assert!(reject(r#"{"kind":"error","text":"","message":"m","children":[]}"#));
+        assert!(reject(r#"{"kind":"markup","text":"a"}"#));
+        assert!(reject(r#"{"kind":"markup"}"#));
+        assert!(!reject(r#"{"kind":"markup","children":[]}"#));
+        assert!(!reject(r#"{"kind":"error","text":"a","message":"m","hints":[]}"#));
+    }
 }
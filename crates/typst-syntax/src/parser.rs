@@ -113,8 +113,10 @@ fn markup_expr(p: &mut Parser, at_start: &mut bool) {
 
         SyntaxKind::Text
         | SyntaxKind::Linebreak
+        | SyntaxKind::Rule
         | SyntaxKind::Escape
         | SyntaxKind::Shorthand
+        | SyntaxKind::Shortcode
         | SyntaxKind::SmartQuote
         | SyntaxKind::Link
         | SyntaxKind::Label => p.eat(),
@@ -122,12 +124,15 @@ fn markup_expr(p: &mut Parser, at_start: &mut bool) {
         SyntaxKind::Hash => embedded_code_expr(p),
         SyntaxKind::Star => strong(p),
         SyntaxKind::Underscore => emph(p),
+        SyntaxKind::StrikeDelim => strike(p),
         SyntaxKind::RawDelim => raw(p),
         SyntaxKind::HeadingMarker if *at_start => heading(p),
         SyntaxKind::ListMarker if *at_start => list_item(p),
         SyntaxKind::EnumMarker if *at_start => enum_item(p),
         SyntaxKind::TermMarker if *at_start => term_item(p),
+        SyntaxKind::TableDelim if *at_start => table(p),
         SyntaxKind::RefMarker => reference(p),
+        SyntaxKind::FootnoteMarker => footnote(p),
         SyntaxKind::Dollar => equation(p),
 
         SyntaxKind::LeftBracket
@@ -136,6 +141,7 @@ fn markup_expr(p: &mut Parser, at_start: &mut bool) {
         | SyntaxKind::ListMarker
         | SyntaxKind::EnumMarker
         | SyntaxKind::TermMarker
+        | SyntaxKind::TableDelim
         | SyntaxKind::Colon => p.convert(SyntaxKind::Text),
 
         _ => {}
@@ -172,6 +178,48 @@ fn emph(p: &mut Parser) {
     p.wrap(m, SyntaxKind::Emph);
 }
 
+/// Parses struck-through content: `~~Struck~~`.
+///
+/// Unlike strong and emphasized content, tildes that can't open are kept as
+/// two non-breaking spaces and a missing closing delimiter is not an error,
+/// as `~~` used to be nothing but that.
+fn strike(p: &mut Parser) {
+    const END: SyntaxSet = SyntaxSet::new()
+        .add(SyntaxKind::StrikeDelim)
+        .add(SyntaxKind::Parbreak)
+        .add(SyntaxKind::RightBracket);
+
+    // The second tilde is lexed as a shorthand on its own afterwards.
+    if !strike_opens(p) {
+        p.eat_as(SyntaxKind::Shorthand, 1);
+        return;
+    }
+
+    let m = p.marker();
+    p.assert(SyntaxKind::StrikeDelim);
+    markup(p, false, 0, |p| {
+        p.at_set(END) && (!p.at(SyntaxKind::StrikeDelim) || strike_closes(p))
+    });
+    p.eat_if(SyntaxKind::StrikeDelim);
+    p.wrap(m, SyntaxKind::Strike);
+}
+
+/// Whether the strike delimiter at the current position can open struck-through
+/// content: It must not directly follow a word or precede whitespace.
+fn strike_opens(p: &Parser) -> bool {
+    let prev = p.text[..p.current_start()].chars().next_back();
+    let next = p.text[p.current_end()..].chars().next();
+    !prev.is_some_and(char::is_alphanumeric) && next.is_some_and(|c| !c.is_whitespace())
+}
+
+/// Whether the strike delimiter at the current position can close struck-through
+/// content: It must not directly follow whitespace or precede a word.
+fn strike_closes(p: &Parser) -> bool {
+    let prev = p.text[..p.current_start()].chars().next_back();
+    let next = p.text[p.current_end()..].chars().next();
+    prev.is_some_and(|c| !c.is_whitespace()) && !next.is_some_and(char::is_alphanumeric)
+}
+
 /// Parses raw text with optional syntax highlighting: `` `...` ``.
 fn raw(p: &mut Parser) {
     let m = p.marker();
@@ -185,6 +233,7 @@ fn raw(p: &mut Parser) {
 
     p.expect(SyntaxKind::RawDelim);
     p.exit();
+    attached_label(p);
     p.wrap(m, SyntaxKind::Raw);
 }
 
@@ -202,19 +251,55 @@ fn heading(p: &mut Parser) {
         p.at_set(END)
             && (!p.at(SyntaxKind::Space) || p.lexer.clone().next() == SyntaxKind::Label)
     });
+    attached_label(p);
     p.wrap(m, SyntaxKind::Heading);
 }
 
+/// Eats a label that directly follows a heading, raw text or an equation in
+/// markup, so that it becomes part of that node: `= Intro <intro>`.
+fn attached_label(p: &mut Parser) {
+    if p.lexer.mode() != LexMode::Markup {
+        return;
+    }
+
+    if p.at(SyntaxKind::Space) && p.lexer.clone().next() == SyntaxKind::Label {
+        p.eat();
+    }
+    p.eat_if(SyntaxKind::Label);
+}
+
 /// Parses an item in a bullet list: `- ...`.
 fn list_item(p: &mut Parser) {
     let m = p.marker();
     let min_indent = p.column(p.current_start()) + 1;
     p.assert(SyntaxKind::ListMarker);
     whitespace_line(p);
+    if p.at(SyntaxKind::LeftBracket) {
+        list_checkbox(p);
+    }
     markup(p, false, min_indent, |p| p.at(SyntaxKind::RightBracket));
     p.wrap(m, SyntaxKind::ListItem);
 }
 
+/// Parses the checkbox of a list item: `[ ]` or `[x]`.
+fn list_checkbox(p: &mut Parser) {
+    let rest = &p.text[p.current_start()..];
+    let Some(after) = ["[ ]", "[x]", "[X]"].iter().find_map(|b| rest.strip_prefix(b))
+    else {
+        return;
+    };
+
+    let separated = match after.chars().next() {
+        Some(c) => c.is_whitespace(),
+        None => true,
+    };
+
+    if separated {
+        p.eat_as(SyntaxKind::ListCheckbox, 3);
+        whitespace_line(p);
+    }
+}
+
 /// Parses an item in an enumeration (numbered list): `+ ...` or `1. ...`.
 fn enum_item(p: &mut Parser) {
     let m = p.marker();
@@ -241,6 +326,63 @@ fn term_item(p: &mut Parser) {
     p.wrap(m, SyntaxKind::TermItem);
 }
 
+/// Parses a table: a header row, a separator line like `|---|---|`, and body
+/// rows, one per line.
+fn table(p: &mut Parser) {
+    let m = p.marker();
+    let column = p.column(p.current_start());
+    p.lexer.set_table(true);
+    table_row(p);
+    if p.at(SyntaxKind::Space)
+        && p.newline()
+        && p.lexer.clone().next() == SyntaxKind::TableSeparator
+    {
+        p.eat();
+        p.eat();
+        while p.at(SyntaxKind::Space)
+            && p.newline()
+            && p.column(p.current_end()) == column
+            && p.lexer.clone().next() == SyntaxKind::TableDelim
+        {
+            p.eat();
+            table_row(p);
+        }
+    }
+    p.lexer.set_table(false);
+    p.wrap(m, SyntaxKind::Table);
+}
+
+/// Parses a row in a table: `| a | b |`.
+fn table_row(p: &mut Parser) {
+    const CELL_END: SyntaxSet = SyntaxSet::new()
+        .add(SyntaxKind::TableDelim)
+        .add(SyntaxKind::RightBracket)
+        .add(SyntaxKind::Eof);
+
+    let m = p.marker();
+    p.assert(SyntaxKind::TableDelim);
+    loop {
+        whitespace_line(p);
+        if p.eof() || p.newline() || p.at(SyntaxKind::RightBracket) {
+            break;
+        }
+
+        // Leave surrounding whitespace out of the cell, like for headings.
+        let cell = p.marker();
+        markup(p, false, usize::MAX, |p| {
+            p.at_set(CELL_END)
+                || (p.at(SyntaxKind::Space) && CELL_END.contains(p.lexer.clone().next()))
+        });
+        p.wrap(cell, SyntaxKind::TableCell);
+
+        whitespace_line(p);
+        if !p.eat_if(SyntaxKind::TableDelim) {
+            break;
+        }
+    }
+    p.wrap(m, SyntaxKind::TableRow);
+}
+
 /// Parses a reference: `@target`, `@target[..]`.
 fn reference(p: &mut Parser) {
     let m = p.marker();
@@ -251,6 +393,14 @@ fn reference(p: &mut Parser) {
     p.wrap(m, SyntaxKind::Ref);
 }
 
+/// Parses a footnote: `^[..]`.
+fn footnote(p: &mut Parser) {
+    let m = p.marker();
+    p.assert(SyntaxKind::FootnoteMarker);
+    content_block(p);
+    p.wrap(m, SyntaxKind::Footnote);
+}
+
 /// Consumes whitespace that does not contain a newline.
 fn whitespace_line(p: &mut Parser) {
     while !p.newline() && p.current().is_trivia() {
@@ -266,6 +416,7 @@ fn equation(p: &mut Parser) {
     math(p, |p| p.at(SyntaxKind::Dollar));
     p.expect_closing_delimiter(m, SyntaxKind::Dollar);
     p.exit();
+    attached_label(p);
     p.wrap(m, SyntaxKind::Equation);
 }
 
@@ -787,10 +938,31 @@ fn code_primary(p: &mut Parser, atomic: bool) {
         | SyntaxKind::Str
         | SyntaxKind::Label => p.eat(),
 
+        SyntaxKind::StrDelim => interpolation(p),
+
         _ => p.expected("expression"),
     }
 }
 
+/// Parses an interpolated string: `f"Hello {name}!"`.
+fn interpolation(p: &mut Parser) {
+    let m = p.marker();
+    p.enter(LexMode::Str);
+    p.assert(SyntaxKind::StrDelim);
+    while !p.eof() && !p.at(SyntaxKind::StrDelim) {
+        if p.at(SyntaxKind::LeftBrace) {
+            code_block(p);
+        } else {
+            p.assert(SyntaxKind::StrPart);
+        }
+    }
+    if !p.eat_if(SyntaxKind::StrDelim) {
+        p[m].convert_to_error("unclosed string");
+    }
+    p.exit();
+    p.wrap(m, SyntaxKind::Interpolation);
+}
+
 /// Parses a content or code block.
 fn block(p: &mut Parser) {
     match p.current() {
@@ -1480,7 +1652,7 @@ struct Parser<'s> {
     current: SyntaxKind,
     balanced: bool,
     nodes: Vec<SyntaxNode>,
-    modes: Vec<LexMode>,
+    modes: Vec<(LexMode, bool)>,
     newline_modes: Vec<NewlineMode>,
     memo: HashMap<usize, (Range<usize>, Checkpoint<'s>)>,
     memo_arena: Vec<SyntaxNode>,
@@ -1616,6 +1788,13 @@ impl<'s> Parser<'s> {
         self.eat();
     }
 
+    /// Eats the next `len` bytes as a single token of the given kind.
+    fn eat_as(&mut self, kind: SyntaxKind, len: usize) {
+        self.lexer.jump(self.current_start + len);
+        self.current = kind;
+        self.eat();
+    }
+
     fn newline(&mut self) -> bool {
         self.lexer.newline()
     }
@@ -1668,16 +1847,20 @@ impl<'s> Parser<'s> {
         self.nodes.insert(from, SyntaxNode::inner(kind, children));
     }
 
+    /// Enter a lexing mode. Pipes only separate table cells in the table's own
+    /// markup, not within nested blocks.
     fn enter(&mut self, mode: LexMode) {
-        self.modes.push(self.lexer.mode());
+        self.modes.push((self.lexer.mode(), self.lexer.table()));
         self.lexer.set_mode(mode);
+        self.lexer.set_table(false);
     }
 
     fn exit(&mut self) {
-        let mode = self.modes.pop().unwrap();
-        if mode != self.lexer.mode() {
+        let (mode, table) = self.modes.pop().unwrap();
+        if mode != self.lexer.mode() || table != self.lexer.table() {
             self.unskip();
             self.lexer.set_mode(mode);
+            self.lexer.set_table(table);
             self.lexer.jump(self.current_start);
             self.lex();
             self.skip();
@@ -0,0 +1,239 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use ecow::EcoString;
+
+use crate::{LinkedNode, SyntaxKind};
+
+/// A structural pattern over syntax trees.
+///
+/// Patterns are built from node kinds and predicates and can require matching
+/// children or descendants. Parts of a pattern can capture the nodes they
+/// match under a name.
+///
+/// ```
+/// # use typst_syntax::{ast, parse, LinkedNode, Pattern, SyntaxKind};
+/// let root = parse("= A `x`\n== B `y`\n= C");
+/// let pattern = Pattern::kind(SyntaxKind::Heading)
+///     .filter(|node| node.cast::<ast::Heading>().unwrap().depth().get() == 1)
+///     .descendant(Pattern::kind(SyntaxKind::Raw).capture("raw"));
+///
+/// let matches = pattern.find(&LinkedNode::new(&root));
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].get("raw").unwrap().get().clone().into_text(), "`x`");
+/// ```
+#[derive(Clone)]
+pub struct Pattern(Repr);
+
+/// The internal representation of a pattern.
+#[derive(Clone)]
+enum Repr {
+    Any,
+    Kind(SyntaxKind),
+    Filter(Arc<dyn Fn(&LinkedNode) -> bool + Send + Sync>),
+    And(Arc<Pattern>, Arc<Pattern>),
+    Or(Arc<Pattern>, Arc<Pattern>),
+    Child(Arc<Pattern>),
+    Descendant(Arc<Pattern>),
+    Capture(EcoString, Arc<Pattern>),
+}
+
+impl Pattern {
+    /// Matches any node.
+    pub fn any() -> Self {
+        Self(Repr::Any)
+    }
+
+    /// Matches nodes of the given kind.
+    pub fn kind(kind: SyntaxKind) -> Self {
+        Self(Repr::Kind(kind))
+    }
+
+    /// Matches nodes for which the predicate holds.
+    pub fn predicate(f: impl Fn(&LinkedNode) -> bool + Send + Sync + 'static) -> Self {
+        Self(Repr::Filter(Arc::new(f)))
+    }
+
+    /// Additionally requires the predicate to hold.
+    pub fn filter(self, f: impl Fn(&LinkedNode) -> bool + Send + Sync + 'static) -> Self {
+        self.and(Self::predicate(f))
+    }
+
+    /// Matches nodes that are matched by both patterns.
+    pub fn and(self, other: Self) -> Self {
+        Self(Repr::And(Arc::new(self), Arc::new(other)))
+    }
+
+    /// Matches nodes that are matched by either pattern.
+    ///
+    /// If both match, only the first one captures nodes.
+    pub fn or(self, other: Self) -> Self {
+        Self(Repr::Or(Arc::new(self), Arc::new(other)))
+    }
+
+    /// Additionally requires a direct child to match the given pattern.
+    ///
+    /// Captures are collected from all matching children.
+    pub fn child(self, child: Self) -> Self {
+        self.and(Self(Repr::Child(Arc::new(child))))
+    }
+
+    /// Additionally requires a descendant to match the given pattern.
+    ///
+    /// Captures are collected from all matching descendants.
+    pub fn descendant(self, descendant: Self) -> Self {
+        self.and(Self(Repr::Descendant(Arc::new(descendant))))
+    }
+
+    /// Captures the nodes matched by this pattern under the given name.
+    pub fn capture(self, name: impl Into<EcoString>) -> Self {
+        Self(Repr::Capture(name.into(), Arc::new(self)))
+    }
+
+    /// Find all nodes in the tree that match the pattern, in source order.
+    pub fn find<'a>(&self, root: &LinkedNode<'a>) -> Vec<Match<'a>> {
+        let mut matches = vec![];
+        self.find_impl(root, &mut matches);
+        matches
+    }
+
+    /// Try to match the pattern at the given node.
+    pub fn matches<'a>(&self, node: &LinkedNode<'a>) -> Option<Match<'a>> {
+        let mut captures = vec![];
+        self.matches_impl(node, &mut captures)
+            .then(|| Match { node: node.clone(), captures })
+    }
+
+    /// Collect the matches in a node and its descendants.
+    fn find_impl<'a>(&self, node: &LinkedNode<'a>, matches: &mut Vec<Match<'a>>) {
+        matches.extend(self.matches(node));
+        for child in node.children() {
+            self.find_impl(&child, matches);
+        }
+    }
+
+    /// Whether the pattern matches the node. Adds the captured nodes on
+    /// success and leaves the captures untouched on failure.
+    fn matches_impl<'a>(
+        &self,
+        node: &LinkedNode<'a>,
+        captures: &mut Vec<(EcoString, LinkedNode<'a>)>,
+    ) -> bool {
+        let len = captures.len();
+        let matched = match &self.0 {
+            Repr::Any => true,
+            Repr::Kind(kind) => node.kind() == *kind,
+            Repr::Filter(f) => f(node),
+            Repr::And(a, b) => {
+                a.matches_impl(node, captures) && b.matches_impl(node, captures)
+            }
+            Repr::Or(a, b) => {
+                a.matches_impl(node, captures) || b.matches_impl(node, captures)
+            }
+            Repr::Child(pattern) => node.children().fold(false, |matched, child| {
+                pattern.matches_impl(&child, captures) || matched
+            }),
+            Repr::Descendant(pattern) => node.children().fold(false, |matched, child| {
+                let here = pattern.matches_impl(&child, captures);
+                let below = self.matches_impl(&child, captures);
+                here || below || matched
+            }),
+            Repr::Capture(name, pattern) => {
+                let matched = pattern.matches_impl(node, captures);
+                if matched {
+                    captures.push((name.clone(), node.clone()));
+                }
+                matched
+            }
+        };
+
+        if !matched {
+            captures.truncate(len);
+        }
+
+        matched
+    }
+}
+
+impl Debug for Pattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.0 {
+            Repr::Any => f.pad("any"),
+            Repr::Kind(kind) => kind.fmt(f),
+            Repr::Filter(_) => f.pad("filter(..)"),
+            Repr::And(a, b) => write!(f, "({a:?} and {b:?})"),
+            Repr::Or(a, b) => write!(f, "({a:?} or {b:?})"),
+            Repr::Child(pattern) => write!(f, "child({pattern:?})"),
+            Repr::Descendant(pattern) => write!(f, "descendant({pattern:?})"),
+            Repr::Capture(name, pattern) => write!(f, "{pattern:?} @{name}"),
+        }
+    }
+}
+
+/// A node matched by a [`Pattern`], together with the captured nodes.
+#[derive(Debug, Clone)]
+pub struct Match<'a> {
+    /// The matched node.
+    pub node: LinkedNode<'a>,
+    /// The captured nodes with their names, in the order they were captured.
+    pub captures: Vec<(EcoString, LinkedNode<'a>)>,
+}
+
+impl<'a> Match<'a> {
+    /// The first node captured under the given name.
+    pub fn get(&self, name: &str) -> Option<&LinkedNode<'a>> {
+        self.captures.iter().find(|(n, _)| n == name).map(|(_, node)| node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[track_caller]
+    fn test(text: &str, pattern: Pattern, name: &str, captured: &[&str]) {
+        let root = parse(text);
+        let matches = pattern.find(&LinkedNode::new(&root));
+        let found: Vec<_> = matches
+            .iter()
+            .flat_map(|m| &m.captures)
+            .filter(|(n, _)| n == name)
+            .map(|(_, node)| node.get().clone().into_text())
+            .collect();
+        assert_eq!(found, captured);
+    }
+
+    #[test]
+    fn test_query_kind() {
+        let strong = Pattern::kind(SyntaxKind::Strong).capture("s");
+        test("*a* _*b*_ c", strong, "s", &["*a*", "*b*"]);
+    }
+
+    #[test]
+    fn test_query_child_and_descendant() {
+        let ident = || Pattern::kind(SyntaxKind::Ident).capture("id");
+        let call = |inner: Pattern| Pattern::kind(SyntaxKind::FuncCall).child(inner);
+        test("#f(g(x))", call(ident()), "id", &["f", "g"]);
+
+        let args = Pattern::kind(SyntaxKind::Args).descendant(ident());
+        test("#f(g(x), y)", args, "id", &["g", "x", "y", "x"]);
+    }
+
+    #[test]
+    fn test_query_failed_match_drops_captures() {
+        let pattern = Pattern::kind(SyntaxKind::Heading)
+            .descendant(Pattern::kind(SyntaxKind::Text).capture("text"))
+            .descendant(Pattern::kind(SyntaxKind::Raw));
+        test("= A\n= B `c`", pattern, "text", &["B", "c"]);
+    }
+
+    #[test]
+    fn test_query_or_and_predicate() {
+        let pattern = Pattern::kind(SyntaxKind::Int)
+            .or(Pattern::kind(SyntaxKind::Float))
+            .filter(|node| node.text().len() > 1)
+            .capture("num");
+        test("#(1, 23, 4.5, 6)", pattern, "num", &["23", "4.5"]);
+    }
+}
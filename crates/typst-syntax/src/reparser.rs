@@ -288,6 +288,12 @@ mod tests {
         test("", 0..0, "do it", false);
         test("a d e", 1..3, " b c d", false);
         test("~*~*~", 2..2, "*", false);
+        test("*x* *y*\n\na ~~b~~ c", 13..14, "d", true);
+        test("*x* *y*\n\n~~a b c", 16..16, "~~", true);
+        test("*x* *y*\n\n~~a b~~ c~~", 14..16, "", false);
+        test("~~a\n\nb~~\n\nc d e", 4..5, "", true);
+        test("*x* *y*\n\n~~a [b~~] c", 14..15, "d", true);
+        test("- [ ] a\n- b", 3..4, "x", true);
         test("::1\n2. a\n3", 7..7, "4", true);
         test("* #{1+2} *", 6..7, "3", true);
         test("#{(0, 1, 2)}", 6..7, "11pt", true);
@@ -303,6 +309,9 @@ mod tests {
         test(r#"a ```typst hello```"#, 16..17, "", false);
         test("a{b}c", 1..1, "#", false);
         test("a#{b}c", 1..2, "", false);
+        test("| a | b |\n|---|---|\n| c |", 22..23, "d", false);
+        test("| a |\n|---|\nb", 12..13, "| b |", false);
+        test("| a |\nb", 6..7, "|---|", false);
     }
 
     #[test]
@@ -318,6 +327,9 @@ mod tests {
         test("A: #[BC]", 6..6, "#{}", true);
         test("#{\"ab\"}A", 5..5, "c", true);
         test("#{\"ab\"}A", 5..6, "c", false);
+        test("#{f\"a{1}b\"}A", 6..7, "x + 2", true);
+        test("#{f\"a{1}b\"}A", 5..5, "\\", true);
+        test("#{\"a{1}b\"}A", 2..2, "f", true);
         test("a#[]b", 3..3, "#{", true);
         test("a#{call(); abc}b", 8..8, "[]", true);
         test("a #while x {\n g(x) \n}  b", 12..12, "//", true);
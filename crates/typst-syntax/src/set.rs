@@ -6,37 +6,43 @@ use crate::SyntaxKind;
 
 /// A set of syntax kinds.
 #[derive(Default, Copy, Clone)]
-pub struct SyntaxSet(u128);
+pub struct SyntaxSet([u128; 2]);
 
 impl SyntaxSet {
     /// Create a new set from a slice of kinds.
     pub const fn new() -> Self {
-        Self(0)
+        Self([0; 2])
     }
 
     /// Insert a syntax kind into the set.
-    ///
-    /// You can only add kinds with discriminator < 128.
     pub const fn add(self, kind: SyntaxKind) -> Self {
-        assert!((kind as u8) < BITS);
-        Self(self.0 | bit(kind))
+        let (word, bit) = locate(kind);
+        let mut words = self.0;
+        words[word] |= bit;
+        Self(words)
     }
 
     /// Combine two syntax sets.
     pub const fn union(self, other: Self) -> Self {
-        Self(self.0 | other.0)
+        Self([self.0[0] | other.0[0], self.0[1] | other.0[1]])
     }
 
     /// Whether the set contains the given syntax kind.
     pub const fn contains(&self, kind: SyntaxKind) -> bool {
-        (kind as u8) < BITS && (self.0 & bit(kind)) != 0
+        let (word, bit) = locate(kind);
+        (self.0[word] & bit) != 0
     }
-}
 
-const BITS: u8 = 128;
+    /// Whether the set contains no kinds.
+    pub const fn is_empty(&self) -> bool {
+        self.0[0] == 0 && self.0[1] == 0
+    }
+}
 
-const fn bit(kind: SyntaxKind) -> u128 {
-    1 << (kind as usize)
+/// The word and bit mask of a kind in a set.
+const fn locate(kind: SyntaxKind) -> (usize, u128) {
+    let index = kind as usize;
+    (index / 128, 1 << (index % 128))
 }
 
 /// Syntax kinds that can start a statement.
@@ -56,8 +62,10 @@ pub const MARKUP_EXPR: SyntaxSet = SyntaxSet::new()
     .add(SyntaxKind::BlockComment)
     .add(SyntaxKind::Text)
     .add(SyntaxKind::Linebreak)
+    .add(SyntaxKind::Rule)
     .add(SyntaxKind::Escape)
     .add(SyntaxKind::Shorthand)
+    .add(SyntaxKind::Shortcode)
     .add(SyntaxKind::SmartQuote)
     .add(SyntaxKind::RawDelim)
     .add(SyntaxKind::Link)
@@ -65,11 +73,14 @@ pub const MARKUP_EXPR: SyntaxSet = SyntaxSet::new()
     .add(SyntaxKind::Hash)
     .add(SyntaxKind::Star)
     .add(SyntaxKind::Underscore)
+    .add(SyntaxKind::StrikeDelim)
     .add(SyntaxKind::HeadingMarker)
     .add(SyntaxKind::ListMarker)
     .add(SyntaxKind::EnumMarker)
     .add(SyntaxKind::TermMarker)
+    .add(SyntaxKind::TableDelim)
     .add(SyntaxKind::RefMarker)
+    .add(SyntaxKind::FootnoteMarker)
     .add(SyntaxKind::Dollar)
     .add(SyntaxKind::LeftBracket)
     .add(SyntaxKind::RightBracket)
@@ -123,6 +134,7 @@ pub const ATOMIC_CODE_PRIMARY: SyntaxSet = SyntaxSet::new()
     .add(SyntaxKind::Bool)
     .add(SyntaxKind::Numeric)
     .add(SyntaxKind::Str)
+    .add(SyntaxKind::StrDelim)
     .add(SyntaxKind::Label)
     .add(SyntaxKind::RawDelim);
 
@@ -183,4 +195,14 @@ mod tests {
         assert!(set.contains(SyntaxKind::Or));
         assert!(!set.contains(SyntaxKind::Not));
     }
+
+    #[test]
+    fn test_set_high_kinds() {
+        let set = SyntaxSet::new().add(SyntaxKind::Closure).add(SyntaxKind::Eof);
+        assert!((SyntaxKind::Eof as u8) >= 128);
+        assert!(set.contains(SyntaxKind::Closure));
+        assert!(set.contains(SyntaxKind::Eof));
+        assert!(!set.contains(SyntaxKind::Markup));
+        assert!(!set.contains(SyntaxKind::Error));
+    }
 }
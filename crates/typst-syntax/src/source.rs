@@ -24,6 +24,7 @@ pub struct Source(Arc<Repr>);
 struct Repr {
     id: FileId,
     text: Prehashed<String>,
+    range: Range<usize>,
     root: Prehashed<SyntaxNode>,
     lines: Vec<Line>,
 }
@@ -31,12 +32,28 @@ struct Repr {
 impl Source {
     /// Create a new source file.
     pub fn new(id: FileId, text: String) -> Self {
-        let mut root = parse(&text);
+        let range = 0..text.len();
+        Self::embedded(id, text, range)
+    }
+
+    /// Create a source file in which only a byte range contains Typst markup,
+    /// for example a fenced block in a Markdown file.
+    ///
+    /// Only the range is parsed, but the source keeps the whole text. Ranges
+    /// and line numbers are thus relative to the whole text, so that spans
+    /// map into the host file.
+    ///
+    /// The method panics if the range is out of bounds or does not lie on
+    /// character boundaries.
+    #[track_caller]
+    pub fn embedded(id: FileId, text: String, range: Range<usize>) -> Self {
+        let mut root = parse(&text[range.clone()]);
         root.numberize(id, Span::FULL).unwrap();
         Self(Arc::new(Repr {
             id,
             lines: lines(&text),
             text: Prehashed::new(text),
+            range,
             root: Prehashed::new(root),
         }))
     }
@@ -47,10 +64,21 @@ impl Source {
     }
 
     /// The root node of the file's untyped syntax tree.
+    ///
+    /// For an [embedded](Self::embedded) source, this covers only the
+    /// [embedded range](Self::embedded_range).
     pub fn root(&self) -> &SyntaxNode {
         &self.0.root
     }
 
+    /// The byte range covered by the syntax tree.
+    ///
+    /// This is the whole text unless the source is
+    /// [embedded](Self::embedded).
+    pub fn embedded_range(&self) -> Range<usize> {
+        self.0.range.clone()
+    }
+
     /// The id of the source file.
     pub fn id(&self) -> FileId {
         self.0.id
@@ -112,6 +140,9 @@ impl Source {
 
     /// Edit the source file by replacing the given range.
     ///
+    /// For an [embedded](Self::embedded) source, edits touching the embedded
+    /// range become part of it and edits before it shift it.
+    ///
     /// Returns the range in the new source that was ultimately reparsed.
     ///
     /// The method panics if the `replace` range is out of bounds.
@@ -141,10 +172,34 @@ impl Source {
             &inner.text[start_byte..],
         ));
 
-        // Incrementally reparse the replaced range.
-        inner
-            .root
-            .update(|root| reparse(root, &inner.text, replace, with.len()))
+        // Edits outside of the embedded range don't affect the tree.
+        let delta = with.len() as isize - replace.len() as isize;
+        let embedded = &mut inner.range;
+        if replace.end < embedded.start {
+            embedded.start = embedded.start.wrapping_add_signed(delta);
+            embedded.end = embedded.end.wrapping_add_signed(delta);
+            return replace.start..replace.start;
+        } else if replace.start > embedded.end {
+            return replace.start..replace.start;
+        }
+
+        // Incrementally reparse the replaced range if it lies in the embedded
+        // range and fully reparse the grown range otherwise.
+        let offset = embedded.start.min(replace.start);
+        let end = embedded.end.max(replace.end).wrapping_add_signed(delta);
+        if offset < embedded.start || replace.end > embedded.end {
+            *embedded = offset..end;
+            let mut root = parse(&inner.text[offset..end]);
+            root.numberize(inner.id, Span::FULL).unwrap();
+            inner.root = Prehashed::new(root);
+            return offset..end;
+        }
+
+        embedded.end = end;
+        let text = &inner.text[offset..end];
+        let local = replace.start - offset..replace.end - offset;
+        let reparsed = inner.root.update(|root| reparse(root, text, local, with.len()));
+        reparsed.start + offset..reparsed.end + offset
     }
 
     /// Get the length of the file in UTF-8 encoded bytes.
@@ -167,7 +222,7 @@ impl Source {
     ///
     /// Returns `None` if the span does not point into this source file.
     pub fn find(&self, span: Span) -> Option<LinkedNode<'_>> {
-        LinkedNode::new(self.root()).find(span)
+        LinkedNode::with_offset(self.root(), self.0.range.start).find(span)
     }
 
     /// Get the byte range for the given span in this file.
@@ -267,6 +322,7 @@ impl Hash for Source {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.id.hash(state);
         self.0.text.hash(state);
+        self.0.range.hash(state);
         self.0.root.hash(state);
     }
 }
@@ -329,6 +385,7 @@ fn len_utf16(string: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Side;
 
     const TEST: &str = "ä\tcde\nf💛g\r\nhi\rjkl";
 
@@ -448,4 +505,39 @@ mod tests {
         // Test removing everything.
         test(TEST, 0..21, "", "");
     }
+
+    #[test]
+    fn test_source_file_embedded() {
+        let text = "# Title\n```typ\n= A *b*\n```\ntail";
+        let mut source = Source::embedded(
+            FileId::new(None, VirtualPath::new("main.md")),
+            text.into(),
+            15..23,
+        );
+        assert_eq!(source.root().len(), 8);
+
+        // Ranges and lines are relative to the whole text.
+        let leaf = LinkedNode::new(source.root()).leaf_at(5, Side::After).unwrap();
+        let span = leaf.span();
+        assert_eq!(leaf.text(), "b");
+        assert_eq!(source.range(span), Some(20..21));
+        assert_eq!(source.byte_to_line(20), Some(2));
+
+        // Edits in front shift the embedded range and edits behind are ignored.
+        source.edit(0..7, "# T");
+        assert_eq!(source.embedded_range(), 11..19);
+        assert_eq!(source.range(span), Some(16..17));
+        assert_eq!(source.edit(23..27, "end"), 23..23);
+        assert_eq!(source.embedded_range(), 11..19);
+
+        // Edits inside grow it.
+        source.edit(18..18, " c");
+        assert_eq!(source.embedded_range(), 11..21);
+        assert_eq!(source.root().clone().into_text(), "= A *b* c\n");
+        assert_eq!(source.text(), "# T\n```typ\n= A *b* c\n```\nend");
+
+        // Edits across its boundary grow it, too.
+        assert_eq!(source.edit(8..12, "\n\n="), 8..20);
+        assert_eq!(source.root().clone().into_text(), "\n\n= A *b* c\n");
+    }
 }
@@ -31,6 +31,7 @@ impl Eval for ast::FuncCall<'_> {
         if !vm.engine.route.within(Route::MAX_CALL_DEPTH) {
             bail!(span, "maximum function call depth exceeded");
         }
+        vm.call(span)?;
 
         // Try to evaluate as a call to an associated function or field.
         let (callee, mut args) = if let ast::Expr::FieldAccess(access) = callee {
@@ -361,6 +362,7 @@ pub(crate) fn call_closure(
 
     // Handle control flow.
     let output = body.eval(&mut vm)?;
+    vm.flush();
     match vm.flow {
         Some(FlowEvent::Return(_, Some(explicit))) => return Ok(explicit),
         Some(FlowEvent::Return(_, None)) => {}
@@ -1,9 +1,10 @@
-use ecow::{eco_vec, EcoVec};
+use ecow::{eco_vec, EcoString, EcoVec};
 
 use crate::diag::{bail, error, At, SourceDiagnostic, SourceResult};
 use crate::eval::{ops, CapturesVisitor, Eval, Vm};
 use crate::foundations::{
-    Array, Capturer, Closure, Content, ContextElem, Dict, Func, NativeElement, Str, Value,
+    repr, Array, Capturer, Closure, Content, ContextElem, Dict, Func, NativeElement, Str,
+    ToStr, Value,
 };
 use crate::syntax::ast::{self, AstNode};
 
@@ -70,6 +71,8 @@ impl Eval for ast::Expr<'_> {
 
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let span = self.span();
+        vm.step(span)?;
+
         let forbidden = |name| {
             error!(span, "{} is only allowed directly in code and content blocks", name)
         };
@@ -78,20 +81,25 @@ impl Eval for ast::Expr<'_> {
             Self::Text(v) => v.eval(vm).map(Value::Content),
             Self::Space(v) => v.eval(vm).map(Value::Content),
             Self::Linebreak(v) => v.eval(vm).map(Value::Content),
+            Self::Rule(v) => v.eval(vm).map(Value::Content),
             Self::Parbreak(v) => v.eval(vm).map(Value::Content),
             Self::Escape(v) => v.eval(vm),
             Self::Shorthand(v) => v.eval(vm),
+            Self::Shortcode(v) => v.eval(vm),
             Self::SmartQuote(v) => v.eval(vm).map(Value::Content),
             Self::Strong(v) => v.eval(vm).map(Value::Content),
             Self::Emph(v) => v.eval(vm).map(Value::Content),
+            Self::Strike(v) => v.eval(vm).map(Value::Content),
             Self::Raw(v) => v.eval(vm).map(Value::Content),
             Self::Link(v) => v.eval(vm).map(Value::Content),
             Self::Label(v) => v.eval(vm),
             Self::Ref(v) => v.eval(vm).map(Value::Content),
+            Self::Footnote(v) => v.eval(vm).map(Value::Content),
             Self::Heading(v) => v.eval(vm).map(Value::Content),
             Self::List(v) => v.eval(vm).map(Value::Content),
             Self::Enum(v) => v.eval(vm).map(Value::Content),
             Self::Term(v) => v.eval(vm).map(Value::Content),
+            Self::Table(v) => v.eval(vm).map(Value::Content),
             Self::Equation(v) => v.eval(vm).map(Value::Content),
             Self::Math(v) => v.eval(vm).map(Value::Content),
             Self::MathIdent(v) => v.eval(vm),
@@ -109,6 +117,7 @@ impl Eval for ast::Expr<'_> {
             Self::Float(v) => v.eval(vm),
             Self::Numeric(v) => v.eval(vm),
             Self::Str(v) => v.eval(vm),
+            Self::Interpolation(v) => v.eval(vm),
             Self::Code(v) => v.eval(vm),
             Self::Content(v) => v.eval(vm).map(Value::Content),
             Self::Array(v) => v.eval(vm).map(Value::Array),
@@ -207,6 +216,28 @@ impl Eval for ast::Str<'_> {
     }
 }
 
+impl Eval for ast::Interpolation<'_> {
+    type Output = Value;
+
+    fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
+        let mut out = EcoString::new();
+        for part in self.parts() {
+            match part {
+                ast::InterpolationPart::Str(part) => out.push_str(&part.get()),
+                ast::InterpolationPart::Code(block) => match block.eval(vm)? {
+                    Value::None => {}
+                    value => match value.cast().at(block.span())? {
+                        ToStr::Str(v) => out.push_str(&v),
+                        ToStr::Int(v) => out.push_str(&repr::format_int_with_base(v, 10)),
+                    },
+                },
+            }
+        }
+
+        Ok(Value::Str(out.into()))
+    }
+}
+
 impl Eval for ast::Array<'_> {
     type Output = Array;
 
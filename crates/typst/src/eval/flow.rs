@@ -74,6 +74,7 @@ impl Eval for ast::WhileLoop<'_> {
             } else if i >= MAX_ITERATIONS {
                 bail!(self.span(), "loop seems to be infinite");
             }
+            check_iterations(vm, i, self.span())?;
 
             let value = body.eval(vm)?;
             output = ops::join(output, value).at(body.span())?;
@@ -112,7 +113,8 @@ impl Eval for ast::ForLoop<'_> {
                 vm.scopes.enter();
 
                 #[allow(unused_parens)]
-                for value in $iterable {
+                for (i, value) in $iterable.into_iter().enumerate() {
+                    check_iterations(vm, i, self.span())?;
                     destructure(vm, $pat, value.into_value())?;
 
                     let body = self.body();
@@ -206,6 +208,14 @@ impl Eval for ast::FuncReturn<'_> {
     }
 }
 
+/// Fail if a loop is about to exceed the configured number of iterations.
+fn check_iterations(vm: &Vm, i: usize, span: Span) -> SourceResult<()> {
+    if let Some(max) = vm.limits.iterations.filter(|&max| i >= max) {
+        bail!(span, "loop exceeded the limit of {max} iterations");
+    }
+    Ok(())
+}
+
 /// Whether the expression always evaluates to the same value.
 fn is_invariant(expr: &SyntaxNode) -> bool {
     match expr.cast() {
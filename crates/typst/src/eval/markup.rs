@@ -1,16 +1,25 @@
-use crate::diag::{warning, SourceResult};
+use smallvec::smallvec;
+
+use crate::diag::{bail, warning, At, SourceResult};
 use crate::eval::{Eval, Vm};
-use crate::foundations::{Content, Label, NativeElement, Smart, Unlabellable, Value};
+use crate::foundations::{
+    Content, Label, NativeElement, Packed, Smart, Unlabellable, Value,
+};
+use crate::layout::{Alignment, Rel, Sizing, TrackSizings};
 use crate::math::EquationElem;
 use crate::model::{
-    EmphElem, EnumItem, HeadingElem, LinkElem, ListItem, ParbreakElem, RefElem,
-    StrongElem, Supplement, TermItem,
+    EmphElem, EnumItem, FootnoteElem, HeadingElem, LinkElem, ListItem, ParbreakElem,
+    RefElem, StrongElem, Supplement, TableCell, TableChild, TableElem, TableHeader,
+    TableItem, TermItem,
 };
 use crate::symbols::Symbol;
 use crate::syntax::ast::{self, AstNode};
 use crate::text::{
-    LinebreakElem, RawContent, RawElem, SmartQuoteElem, SpaceElem, TextElem,
+    LinebreakElem, RawContent, RawElem, RawLineRange, SmartQuoteElem, SpaceElem,
+    StrikeElem, TextElem,
 };
+use crate::visualize::LineElem;
+use crate::World;
 
 impl Eval for ast::Markup<'_> {
     type Output = Content;
@@ -95,6 +104,14 @@ impl Eval for ast::Linebreak<'_> {
     }
 }
 
+impl Eval for ast::Rule<'_> {
+    type Output = Content;
+
+    fn eval(self, _: &mut Vm) -> SourceResult<Self::Output> {
+        Ok(LineElem::new().with_length(Rel::one()).pack())
+    }
+}
+
 impl Eval for ast::Parbreak<'_> {
     type Output = Content;
 
@@ -119,6 +136,33 @@ impl Eval for ast::Shorthand<'_> {
     }
 }
 
+impl Eval for ast::Shortcode<'_> {
+    type Output = Value;
+
+    fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
+        // Look in the `emoji` binding first so that users can shadow it with
+        // their own shortcodes, then fall back to the standard emoji.
+        let name = self.get();
+        let library = vm.engine.world.library();
+        let found = [vm.scopes.get("emoji").ok(), library.global.scope().get("emoji")]
+            .into_iter()
+            .flatten()
+            .find_map(|table| {
+                name.split('.')
+                    .try_fold(table.clone(), |value, part| value.field(part).ok())
+            });
+
+        // Unknown shortcodes are likely just text between colons.
+        Ok(found.unwrap_or_else(|| {
+            vm.engine.tracer.warn(warning!(
+                self.span(), "unknown shortcode `{name}`";
+                hint: "it is kept as text, escape a colon to silence this warning",
+            ));
+            Value::Content(TextElem::packed(self.to_untyped().text().clone()))
+        }))
+    }
+}
+
 impl Eval for ast::SmartQuote<'_> {
     type Output = Content;
 
@@ -163,6 +207,18 @@ impl Eval for ast::Emph<'_> {
     }
 }
 
+impl Eval for ast::Strike<'_> {
+    type Output = Content;
+
+    fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
+        let body = self.body().eval(vm)?;
+        if !self.closed() {
+            return Ok(TextElem::packed("\u{a0}\u{a0}") + body);
+        }
+        Ok(StrikeElem::new(body).pack())
+    }
+}
+
 impl Eval for ast::Raw<'_> {
     type Output = Content;
 
@@ -172,7 +228,44 @@ impl Eval for ast::Raw<'_> {
         if let Some(lang) = self.lang() {
             elem.push_lang(Some(lang.get().clone()));
         }
-        Ok(elem.pack())
+        if let Some(meta) = self.meta() {
+            let too_large = "line number is too large";
+            let number =
+                |n: usize| i64::try_from(n).map_err(|_| too_large).at(meta.span());
+
+            if let Some(range) = meta.line_range() {
+                let range = range.map_err(|_| too_large).at(meta.span())?;
+                let (start, end) = range.into_inner();
+                let range =
+                    RawLineRange::new(number(start)?, number(end)?).at(meta.span())?;
+                elem.push_line_range(Some(range));
+            }
+
+            // Only expand highlighted ranges within the raw text, so that huge
+            // ranges don't exhaust the memory. The first line beyond the text
+            // is kept, so that it fails with a helpful error when shown.
+            let count = self.lines().count();
+            let mut lines = vec![];
+            for range in meta.highlight_lines().map_err(|_| too_large).at(meta.span())? {
+                let (start, end) = range.into_inner();
+                if start > end {
+                    bail!(
+                        meta.span(),
+                        "highlighted line range must not end before it starts"
+                    );
+                }
+                lines.extend((start..=end.min(count)).map(|line| line as i64));
+                if end > count {
+                    lines.push(number(start.max(count + 1))?);
+                }
+            }
+            elem.push_highlight_lines(lines);
+        }
+        let mut raw = elem.pack();
+        if let Some(label) = self.label() {
+            raw = raw.labelled(Label::new(label.get()));
+        }
+        Ok(raw)
     }
 }
 
@@ -207,13 +300,26 @@ impl Eval for ast::Ref<'_> {
     }
 }
 
+impl Eval for ast::Footnote<'_> {
+    type Output = Content;
+
+    fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
+        let body = self.body().eval(vm)?;
+        Ok(FootnoteElem::with_content(body).pack())
+    }
+}
+
 impl Eval for ast::Heading<'_> {
     type Output = Content;
 
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let depth = self.depth();
         let body = self.body().eval(vm)?;
-        Ok(HeadingElem::new(body).with_depth(depth).pack())
+        let mut heading = HeadingElem::new(body).with_depth(depth).pack();
+        if let Some(label) = self.label() {
+            heading = heading.labelled(Label::new(label.get()));
+        }
+        Ok(heading)
     }
 }
 
@@ -221,7 +327,11 @@ impl Eval for ast::ListItem<'_> {
     type Output = Content;
 
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
-        Ok(ListItem::new(self.body().eval(vm)?).pack())
+        let mut item = ListItem::new(self.body().eval(vm)?);
+        if let Some(checked) = self.checked() {
+            item.push_checked(Some(checked));
+        }
+        Ok(item.pack())
     }
 }
 
@@ -248,12 +358,61 @@ impl Eval for ast::TermItem<'_> {
     }
 }
 
+impl Eval for ast::Table<'_> {
+    type Output = Content;
+
+    fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
+        let separator = self.separator();
+        let alignments = separator.map(|sep| sep.alignments()).unwrap_or_default();
+
+        let mut columns = 0;
+        let mut header = vec![];
+        let mut children = vec![];
+        for (y, row) in self.rows().enumerate() {
+            for (x, cell) in row.cells().enumerate() {
+                let body = cell.body().eval(vm)?;
+                let mut elem = TableCell::new(body)
+                    .with_x(Smart::Custom(x))
+                    .with_y(Smart::Custom(y));
+                if let Some(Some(alignment)) = alignments.get(x) {
+                    elem.push_align(Smart::Custom(match alignment {
+                        ast::TableAlignment::Left => Alignment::LEFT,
+                        ast::TableAlignment::Center => Alignment::CENTER,
+                        ast::TableAlignment::Right => Alignment::RIGHT,
+                    }));
+                }
+
+                let item = TableItem::Cell(Packed::new(elem).spanned(cell.span()));
+                if y == 0 && separator.is_some() {
+                    header.push(item);
+                } else {
+                    children.push(TableChild::Item(item));
+                }
+                columns = columns.max(x + 1);
+            }
+        }
+
+        if !header.is_empty() {
+            let header = Packed::new(TableHeader::new(header)).spanned(self.span());
+            children.insert(0, TableChild::Header(header));
+        }
+
+        Ok(TableElem::new(children)
+            .with_columns(TrackSizings(smallvec![Sizing::Auto; columns]))
+            .pack())
+    }
+}
+
 impl Eval for ast::Equation<'_> {
     type Output = Content;
 
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let body = self.body().eval(vm)?;
         let block = self.block();
-        Ok(EquationElem::new(body).with_block(block).pack())
+        let mut equation = EquationElem::new(body).with_block(block).pack();
+        if let Some(label) = self.label() {
+            equation = equation.labelled(Label::new(label.get()));
+        }
+        Ok(equation)
     }
 }
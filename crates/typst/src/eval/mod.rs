@@ -74,6 +74,7 @@ pub fn eval(
     // Evaluate the module.
     let markup = root.cast::<ast::Markup>().unwrap();
     let output = markup.eval(&mut vm)?;
+    vm.flush();
 
     // Handle control flow.
     if let Some(flow) = vm.flow {
@@ -146,6 +147,7 @@ pub fn eval_string(
                 .pack(),
         ),
     };
+    vm.flush();
 
     // Handle control flow.
     if let Some(flow) = vm.flow {
@@ -14,17 +14,18 @@ impl Eval for ast::SetRule<'_> {
         }
 
         let target = self.target();
-        let target = target
-            .eval(vm)?
-            .cast::<Func>()
-            .and_then(|func| {
-                func.element().ok_or_else(|| {
-                    "only element functions can be used in set rules".into()
-                })
-            })
+        let func = target.eval(vm)?.cast::<Func>().at(target.span())?;
+        if let Some(custom) = func.custom() {
+            let args = self.args().eval(vm)?.spanned(self.span());
+            return Ok(custom.set(args)?.spanned(self.span()));
+        }
+
+        let element = func
+            .element()
+            .ok_or("only element functions can be used in set rules")
             .at(target.span())?;
         let args = self.args().eval(vm)?.spanned(self.span());
-        Ok(target.set(&mut vm.engine, args)?.spanned(self.span()))
+        Ok(element.set(&mut vm.engine, args)?.spanned(self.span()))
     }
 }
 
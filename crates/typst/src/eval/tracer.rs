@@ -1,13 +1,15 @@
 use std::collections::HashSet;
 
-use ecow::EcoVec;
+use ecow::{eco_format, EcoString, EcoVec};
 
 use crate::diag::SourceDiagnostic;
+use crate::eval::Limits;
 use crate::foundations::{Styles, Value};
 use crate::syntax::{FileId, Span};
 use crate::util::hash128;
 
-/// Traces warnings and which values existed for an expression at a span.
+/// Traces warnings, the work done by evaluation, and which values existed for
+/// an expression at a span.
 #[derive(Default, Clone)]
 pub struct Tracer {
     inspected: Option<Span>,
@@ -15,6 +17,8 @@ pub struct Tracer {
     warnings_set: HashSet<u128>,
     delayed: EcoVec<SourceDiagnostic>,
     values: EcoVec<(Value, Option<Styles>)>,
+    steps: usize,
+    calls: usize,
 }
 
 impl Tracer {
@@ -73,6 +77,25 @@ impl Tracer {
         }
     }
 
+    /// Account for evaluated expressions and function calls.
+    pub fn work(&mut self, steps: usize, calls: usize) {
+        self.steps += steps;
+        self.calls += calls;
+    }
+
+    /// Describes the limit that the work accounted for so far exceeds, if any.
+    pub fn exceeded(&self, limits: Limits) -> Option<EcoString> {
+        if let Some(max) = limits.steps.filter(|&max| self.steps > max) {
+            return Some(eco_format!("evaluation exceeded the limit of {max} steps"));
+        }
+        if let Some(max) = limits.calls.filter(|&max| self.calls > max) {
+            return Some(eco_format!(
+                "evaluation exceeded the limit of {max} function calls"
+            ));
+        }
+        None
+    }
+
     /// Trace a value for the span.
     pub fn value(&mut self, value: Value, styles: Option<Styles>) {
         if self.values.len() < Self::MAX_VALUES {
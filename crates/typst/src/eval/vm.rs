@@ -1,5 +1,6 @@
 use comemo::Tracked;
 
+use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
 use crate::eval::FlowEvent;
 use crate::foundations::{Context, IntoValue, Scopes, Value};
@@ -22,9 +23,18 @@ pub struct Vm<'a> {
     pub(crate) inspected: Option<Span>,
     /// Data that is contextually made accessible to code behind the scenes.
     pub(crate) context: Tracked<'a, Context<'a>>,
+    /// The limits on the work this machine may do.
+    pub(crate) limits: Limits,
+    /// The number of expressions evaluated and not yet accounted for.
+    steps: usize,
+    /// The number of function calls made and not yet accounted for.
+    calls: usize,
 }
 
 impl<'a> Vm<'a> {
+    /// How much work is batched before it is accounted for in the engine.
+    const BATCH: usize = 1024;
+
     /// Create a new virtual machine.
     pub fn new(
         engine: Engine<'a>,
@@ -33,7 +43,17 @@ impl<'a> Vm<'a> {
         target: Span,
     ) -> Self {
         let inspected = target.id().and_then(|id| engine.tracer.inspected(id));
-        Self { engine, context, flow: None, scopes, inspected }
+        let limits = engine.world.library().limits;
+        Self {
+            engine,
+            context,
+            flow: None,
+            scopes,
+            inspected,
+            limits,
+            steps: 0,
+            calls: 0,
+        }
     }
 
     /// Access the underlying world.
@@ -50,6 +70,40 @@ impl<'a> Vm<'a> {
         self.scopes.top.define(var.get().clone(), value);
     }
 
+    /// Count the evaluation of an expression, failing if the compilation
+    /// evaluated too many.
+    pub(crate) fn step(&mut self, span: Span) -> SourceResult<()> {
+        self.steps += 1;
+        self.check(span)
+    }
+
+    /// Count a function call, failing if the compilation made too many.
+    pub(crate) fn call(&mut self, span: Span) -> SourceResult<()> {
+        self.calls += 1;
+        self.check(span)
+    }
+
+    /// Account for a full batch of work and check it against the limits.
+    fn check(&mut self, span: Span) -> SourceResult<()> {
+        if self.steps + self.calls >= Self::BATCH {
+            self.flush();
+            if let Some(message) = self.engine.tracer.exceeded(self.limits) {
+                bail!(span, "{message}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Account for the remaining work in the engine. Called once the machine
+    /// is done.
+    pub(crate) fn flush(&mut self) {
+        if self.steps > 0 || self.calls > 0 {
+            let steps = std::mem::take(&mut self.steps);
+            let calls = std::mem::take(&mut self.calls);
+            self.engine.tracer.work(steps, calls);
+        }
+    }
+
     /// Trace a value.
     #[cold]
     pub fn trace(&mut self, value: Value) {
@@ -58,3 +112,25 @@ impl<'a> Vm<'a> {
             .value(value.clone(), self.context.styles().ok().map(|s| s.to_map()));
     }
 }
+
+/// Limits on the work that evaluation may do.
+///
+/// Configure them through
+/// [`LibraryBuilder::with_limits`](crate::LibraryBuilder::with_limits) to
+/// protect against documents that never finish evaluating, for instance when
+/// compiling untrusted input. When a limit is exceeded, evaluation fails with
+/// an error. By default, there are no limits.
+///
+/// Loop iterations are counted per loop. Function calls and steps are counted
+/// for the whole compilation, including work reused from earlier compilations.
+/// To stay cheap, they are checked in batches, so evaluation may slightly
+/// overshoot a limit before it fails.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Limits {
+    /// The maximum number of iterations of a single loop.
+    pub iterations: Option<usize>,
+    /// The maximum number of function calls in one compilation.
+    pub calls: Option<usize>,
+    /// The maximum number of expressions evaluated in one compilation.
+    pub steps: Option<usize>,
+}
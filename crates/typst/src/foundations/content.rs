@@ -14,9 +14,9 @@ use smallvec::smallvec;
 use crate::diag::{SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, func, scope, ty, Context, Dict, Element, Fields, IntoValue, Label,
-    NativeElement, Recipe, RecipeIndex, Repr, Selector, Str, Style, StyleChain, Styles,
-    Value,
+    elem, func, scope, ty, Context, CustomElem, Dict, Element, Fields, Func, IntoValue,
+    Label, NativeElement, Recipe, RecipeIndex, Repr, Selector, Str, Style, StyleChain,
+    Styles, Value,
 };
 use crate::introspection::{Location, Meta, MetaElem};
 use crate::layout::{AlignElem, Alignment, Axes, Length, MoveElem, PadElem, Rel, Sides};
@@ -186,6 +186,9 @@ impl Content {
                 return Some(label.into_value());
             }
         }
+        if let Some(custom) = self.to_packed::<CustomElem>() {
+            return custom.field(id);
+        }
         match styles {
             Some(styles) => self.inner.elem.field_with_styles(id, styles),
             None => self.inner.elem.field(id),
@@ -202,6 +205,9 @@ impl Content {
                 return Some(label.into_value());
             }
         }
+        if let Some(custom) = self.to_packed::<CustomElem>() {
+            return custom.fields.get(name).ok().cloned();
+        }
         let id = self.elem().field_id(name)?;
         self.get(id, None)
     }
@@ -212,8 +218,13 @@ impl Content {
     /// if you have set the field IDs yourself or are using the field IDs
     /// generated by the `#[elem]` macro.
     pub fn field(&self, id: u8) -> StrResult<Value> {
-        self.get(id, None)
-            .ok_or_else(|| missing_field(self.elem().field_name(id).unwrap()))
+        self.get(id, None).ok_or_else(|| {
+            let name = match self.to_packed::<CustomElem>() {
+                Some(custom) => custom.definition.field_name(id),
+                None => self.elem().field_name(id),
+            };
+            missing_field(name.unwrap())
+        })
     }
 
     /// Get a field by name, returning a missing field error if it does not
@@ -513,8 +524,11 @@ impl Content {
     /// a specific
     /// kind of element.
     #[func]
-    pub fn func(&self) -> Element {
-        self.elem()
+    pub fn func(&self) -> Func {
+        match self.to_packed::<CustomElem>() {
+            Some(custom) => custom.definition.clone().into(),
+            None => self.elem().into(),
+        }
     }
 
     /// Whether the content has the specified field.
@@ -528,6 +542,10 @@ impl Content {
             return self.label().is_some();
         }
 
+        if let Some(custom) = self.to_packed::<CustomElem>() {
+            return custom.fields.contains(&field);
+        }
+
         let Some(id) = self.elem().field_id(&field) else {
             return false;
         };
@@ -562,7 +580,10 @@ impl Content {
     /// ```
     #[func]
     pub fn fields(&self) -> Dict {
-        let mut dict = self.inner.elem.fields();
+        let mut dict = match self.to_packed::<CustomElem>() {
+            Some(custom) => custom.fields.clone(),
+            None => self.inner.elem.fields(),
+        };
         if let Some(label) = self.label() {
             dict.insert("label".into(), label.into_value());
         }
@@ -0,0 +1,281 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use comemo::Track;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, func, repr, Args, Construct, Content, Context, Dict, Func, IntoValue,
+    NativeElement, Packed, Repr, Selector, Show, Str, StyleChain, Styles, Synthesize,
+    Value,
+};
+use crate::introspection::Locatable;
+
+/// Defines a new kind of element.
+///
+/// The result is an element function: Calling it creates an element of the
+/// new kind and you can use it in set and show rules and with queries and
+/// counters, just like the built-in element functions. This lets packages
+/// provide elements that downstream documents can restyle.
+///
+/// The element's fields are given after the `display` function. A string
+/// declares a required field, which must be passed when creating an element
+/// and can be passed positionally. A named argument declares an optional
+/// field with the given default value, which can also be configured with set
+/// rules.
+///
+/// ```example
+/// #let theorem = element(
+///   "theorem",
+///   it => block[*#it.supplement.* #it.body],
+///   "body",
+///   supplement: [Theorem],
+/// )
+///
+/// #theorem[Every prime is odd.]
+///
+/// #set theorem(supplement: [Conjecture])
+/// #show theorem: set text(blue)
+/// #theorem[Every odd number is prime.]
+/// ```
+#[func]
+pub fn element(
+    /// The real arguments (the other argument is just for the docs).
+    /// The docs argument cannot be called `args`.
+    args: &mut Args,
+    /// The element's name.
+    name: Str,
+    /// Displays an element of this kind. It is called with the element, whose
+    /// optional fields are resolved with the active set rules.
+    display: Func,
+    /// The names of the required fields and the optional fields with their
+    /// default values.
+    #[external]
+    #[variadic]
+    fields: Vec<Value>,
+) -> SourceResult<Func> {
+    let span = args.span;
+    let required = args.all::<Str>()?;
+    let optional = args.to_named();
+    args.items.retain(|arg| arg.name.is_none());
+
+    let mut fields: Vec<(EcoString, Option<Value>)> = vec![];
+    for name in required {
+        fields.push((name.into(), None));
+    }
+    for (name, default) in optional {
+        fields.push((name.into(), Some(default)));
+    }
+
+    for (i, (field, _)) in fields.iter().enumerate() {
+        if field == "label" {
+            bail!(span, "cannot declare field `label`");
+        } else if fields[..i].iter().any(|(prev, _)| prev == field) {
+            bail!(span, "duplicate field: {field}");
+        }
+    }
+
+    if fields.len() >= DEFINITION.into() {
+        bail!(span, "too many fields");
+    }
+
+    let definition =
+        CustomElement(Arc::new(Inner { name: name.into(), display, fields }));
+    Ok(Func::from(definition).spanned(span))
+}
+
+/// The field ID through which selectors match the definition of an element.
+const DEFINITION: u8 = 254;
+
+/// A kind of element defined with the [`element`] function.
+#[derive(Clone, PartialEq, Hash)]
+pub struct CustomElement(Arc<Inner>);
+
+/// The internal representation of a [`CustomElement`].
+#[derive(PartialEq, Hash)]
+struct Inner {
+    /// The element's name.
+    name: EcoString,
+    /// Displays an element of this kind.
+    display: Func,
+    /// The fields with their default values, the required ones first.
+    fields: Vec<(EcoString, Option<Value>)>,
+}
+
+impl CustomElement {
+    /// The element's name.
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// Extract the field ID for the given field name.
+    pub fn field_id(&self, name: &str) -> Option<u8> {
+        if name == "label" {
+            return Some(255);
+        }
+        let index = self.0.fields.iter().position(|(field, _)| field == name)?;
+        Some(index as u8)
+    }
+
+    /// Extract the field name for the given field ID.
+    pub fn field_name(&self, id: u8) -> Option<&str> {
+        if id == 255 {
+            return Some("label");
+        }
+        self.0.fields.get(usize::from(id)).map(|(field, _)| field.as_str())
+    }
+
+    /// Create an element of this kind from the arguments.
+    pub fn construct(&self, args: &mut Args) -> SourceResult<Content> {
+        let mut fields = Dict::new();
+        for (field, default) in &self.0.fields {
+            let value = match args.named::<Value>(field)? {
+                Some(value) => value,
+                None if default.is_none() => args.expect(field)?,
+                None => continue,
+            };
+            fields.insert(field.as_str().into(), value);
+        }
+        Ok(CustomElem::new(self.clone(), fields).pack().spanned(args.span))
+    }
+
+    /// Execute a set rule for elements of this kind and return the resulting
+    /// style map.
+    pub fn set(&self, mut args: Args) -> SourceResult<Styles> {
+        let mut fields = Dict::new();
+        for (field, _) in self.0.fields.iter().filter(|(_, default)| default.is_some()) {
+            if let Some(value) = args.named::<Value>(field)? {
+                fields.insert(field.as_str().into(), value);
+            }
+        }
+        args.finish()?;
+
+        let mut styles = Styles::new();
+        if !fields.is_empty() {
+            styles.set(CustomElem::set_settings(vec![(self.clone(), fields)]));
+        }
+        Ok(styles)
+    }
+
+    /// Create a selector for elements of this kind.
+    pub fn select(&self) -> Selector {
+        self.where_([])
+    }
+
+    /// Create a selector for elements of this kind whose fields have the
+    /// given values.
+    pub fn where_(&self, fields: impl IntoIterator<Item = (u8, Value)>) -> Selector {
+        let definition = (DEFINITION, Func::from(self.clone()).into_value());
+        let fields = std::iter::once(definition).chain(fields).collect();
+        Selector::Elem(CustomElem::elem(), Some(fields))
+    }
+
+    /// The repr of a selector for elements of this kind with the given
+    /// fields.
+    pub fn repr_selector(&self, fields: &[(u8, Value)]) -> EcoString {
+        let fields: Dict = fields
+            .iter()
+            .filter(|(id, _)| *id != DEFINITION)
+            .filter_map(|(id, value)| Some((self.field_name(*id)?.into(), value.clone())))
+            .collect();
+        if fields.is_empty() {
+            self.name().into()
+        } else {
+            eco_format!("{}.where{}", self.name(), fields.repr())
+        }
+    }
+}
+
+impl Debug for CustomElement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "CustomElement({})", self.name())
+    }
+}
+
+/// An element of a kind defined with the [`element`] function.
+#[elem(Construct, Repr, Locatable, Synthesize, Show)]
+pub struct CustomElem {
+    /// The element's kind.
+    #[required]
+    #[internal]
+    pub definition: CustomElement,
+
+    /// The element's fields. Before synthesis, these are only the explicitly
+    /// passed ones.
+    #[required]
+    #[internal]
+    pub fields: Dict,
+
+    /// The fields configured by set rules, for all kinds of custom elements.
+    #[internal]
+    #[fold]
+    #[ghost]
+    settings: Vec<(CustomElement, Dict)>,
+}
+
+impl CustomElem {
+    /// Get a field by ID, like [`Content::get`].
+    pub fn field(&self, id: u8) -> Option<Value> {
+        if id == DEFINITION {
+            return Some(Func::from(self.definition.clone()).into_value());
+        }
+        self.fields.get(self.definition.field_name(id)?).ok().cloned()
+    }
+}
+
+impl Construct for CustomElem {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
+impl Synthesize for Packed<CustomElem> {
+    fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
+        let settings = CustomElem::settings_in(styles);
+        let definition = self.definition.clone();
+        for (field, default) in &definition.0.fields {
+            let key: Str = field.as_str().into();
+            if self.fields.contains(&key) {
+                continue;
+            }
+            let value = settings
+                .iter()
+                .rev()
+                .filter(|(kind, _)| *kind == definition)
+                .find_map(|(_, fields)| fields.get(&key).ok())
+                .or(default.as_ref());
+            if let Some(value) = value.cloned() {
+                self.fields.insert(key, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Show for Packed<CustomElem> {
+    #[typst_macros::time(name = "custom", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let context = Context::new(self.location(), Some(styles));
+        let display = &self.definition.0.display;
+        Ok(display
+            .call(engine, context.track(), [self.clone().pack()])?
+            .display())
+    }
+}
+
+impl Repr for CustomElem {
+    fn repr(&self) -> EcoString {
+        let fields: Vec<_> = self
+            .fields
+            .iter()
+            .map(|(name, value)| eco_format!("{}: {}", name, value.repr()))
+            .collect();
+        eco_format!(
+            "{}{}",
+            self.definition.name(),
+            repr::pretty_array_like(&fields, false)
+        )
+    }
+}
@@ -8,8 +8,8 @@ use once_cell::sync::Lazy;
 use crate::diag::{bail, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, repr, scope, ty, Args, CastInfo, Content, Context, Element, IntoArgs, Scope,
-    Selector, Type, Value,
+    cast, repr, scope, ty, Args, CastInfo, Content, Context, CustomElement, Element,
+    IntoArgs, Scope, Selector, Type, Value,
 };
 use crate::syntax::{ast, Span, SyntaxNode};
 use crate::util::{LazyHash, Static};
@@ -140,6 +140,8 @@ enum Repr {
     Native(Static<NativeFuncData>),
     /// A function for an element.
     Element(Element),
+    /// A function for a user-defined element.
+    Custom(CustomElement),
     /// A user-defined closure.
     Closure(Arc<LazyHash<Closure>>),
     /// A nested function with pre-applied arguments.
@@ -154,6 +156,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(native.name),
             Repr::Element(elem) => Some(elem.name()),
+            Repr::Custom(custom) => Some(custom.name()),
             Repr::Closure(closure) => closure.name(),
             Repr::With(with) => with.0.name(),
         }
@@ -166,7 +169,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(native.title),
             Repr::Element(elem) => Some(elem.title()),
-            Repr::Closure(_) => None,
+            Repr::Custom(_) | Repr::Closure(_) => None,
             Repr::With(with) => with.0.title(),
         }
     }
@@ -176,7 +179,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(native.docs),
             Repr::Element(elem) => Some(elem.docs()),
-            Repr::Closure(_) => None,
+            Repr::Custom(_) | Repr::Closure(_) => None,
             Repr::With(with) => with.0.docs(),
         }
     }
@@ -194,7 +197,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(&native.0.params),
             Repr::Element(elem) => Some(elem.params()),
-            Repr::Closure(_) => None,
+            Repr::Custom(_) | Repr::Closure(_) => None,
             Repr::With(with) => with.0.params(),
         }
     }
@@ -210,7 +213,7 @@ impl Func {
             Lazy::new(|| CastInfo::Type(Type::of::<Content>()));
         match &self.repr {
             Repr::Native(native) => Some(&native.0.returns),
-            Repr::Element(_) => Some(&CONTENT),
+            Repr::Element(_) | Repr::Custom(_) => Some(&CONTENT),
             Repr::Closure(_) => None,
             Repr::With(with) => with.0.returns(),
         }
@@ -221,7 +224,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => native.keywords,
             Repr::Element(elem) => elem.keywords(),
-            Repr::Closure(_) => &[],
+            Repr::Custom(_) | Repr::Closure(_) => &[],
             Repr::With(with) => with.0.keywords(),
         }
     }
@@ -231,7 +234,7 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => Some(&native.0.scope),
             Repr::Element(elem) => Some(elem.scope()),
-            Repr::Closure(_) => None,
+            Repr::Custom(_) | Repr::Closure(_) => None,
             Repr::With(with) => with.0.scope(),
         }
     }
@@ -257,6 +260,14 @@ impl Func {
         }
     }
 
+    /// Extract the user-defined element, if it is one.
+    pub fn custom(&self) -> Option<&CustomElement> {
+        match &self.repr {
+            Repr::Custom(custom) => Some(custom),
+            _ => None,
+        }
+    }
+
     /// Call the function with the given context and arguments.
     pub fn call<A: IntoArgs>(
         &self,
@@ -286,6 +297,11 @@ impl Func {
                 args.finish()?;
                 Ok(Value::Content(value))
             }
+            Repr::Custom(custom) => {
+                let value = custom.construct(&mut args)?;
+                args.finish()?;
+                Ok(Value::Content(value))
+            }
             Repr::Closure(closure) => crate::eval::call_closure(
                 self,
                 closure,
@@ -362,6 +378,22 @@ impl Func {
         let fields = args.to_named();
         args.items.retain(|arg| arg.name.is_none());
 
+        if let Some(custom) = self.custom() {
+            let fields = fields
+                .into_iter()
+                .map(|(key, value)| {
+                    custom.field_id(&key).map(|id| (id, value)).ok_or_else(|| {
+                        eco_format!(
+                            "element `{}` does not have field `{}`",
+                            custom.name(),
+                            key
+                        )
+                    })
+                })
+                .collect::<StrResult<Vec<_>>>()?;
+            return Ok(custom.where_(fields));
+        }
+
         let element = self
             .element()
             .ok_or("`where()` can only be called on element functions")?;
@@ -425,6 +457,12 @@ impl From<Element> for Func {
     }
 }
 
+impl From<CustomElement> for Func {
+    fn from(custom: CustomElement) -> Self {
+        Repr::Custom(custom).into()
+    }
+}
+
 /// A Typst function that is defined by a native Rust type that shadows a
 /// native Rust function.
 pub trait NativeFunc {
@@ -1,6 +1,7 @@
 //! Foundational types and functions.
 
 pub mod calc;
+pub mod random;
 pub mod repr;
 pub mod sys;
 
@@ -12,6 +13,7 @@ mod bytes;
 mod cast;
 mod content;
 mod context;
+mod custom;
 mod datetime;
 mod dict;
 mod duration;
@@ -40,6 +42,7 @@ pub use self::bytes::*;
 pub use self::cast::*;
 pub use self::content::*;
 pub use self::context::*;
+pub use self::custom::{CustomElem, CustomElement};
 pub use self::datetime::*;
 pub use self::dict::*;
 pub use self::duration::*;
@@ -111,7 +114,9 @@ pub(super) fn define(global: &mut Scope, inputs: Dict) {
     global.define_func::<assert>();
     global.define_func::<eval>();
     global.define_func::<style>();
+    global.define_func::<custom::element>();
     global.define_module(calc::module());
+    global.define_module(random::module());
     global.define_module(sys::module(inputs));
 }
 
@@ -0,0 +1,130 @@
+//! Seeded pseudo-random number generation.
+
+use ecow::EcoString;
+
+use crate::diag::{bail, SourceResult};
+use crate::foundations::{array, func, scope, ty, Array, Module, Repr, Scope, Value};
+use crate::syntax::Spanned;
+
+/// A module with seeded pseudo-random number generation.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_func::<gen>();
+    scope.define_type::<Generator>();
+    Module::new("random", scope)
+}
+
+/// Creates a pseudo-random number generator from a seed.
+///
+/// Generators are plain values: Each method returns the drawn value together
+/// with the generator to draw the next value from. Destructure the result and
+/// keep using the returned generator to get a stream of numbers. The same seed
+/// always yields the same stream.
+///
+/// - `float()` generates a float between zero (inclusive) and one
+///   (exclusive).
+/// - `int(start, end)` generates an integer from `start` (inclusive) to `end`
+///   (exclusive).
+/// - `shuffle(array)` shuffles an array into a random order.
+///
+/// ```example
+/// #let rng = random.gen(42)
+/// #let (a, rng) = rng.int(1, 7)
+/// #let (b, rng) = rng.int(1, 7)
+/// #let (x, rng) = rng.float()
+/// #let (items, rng) = rng.shuffle((1, 2, 3, 4, 5))
+/// #a, #b \
+/// #x \
+/// #items
+/// ```
+#[func]
+pub fn gen(
+    /// The seed of the generator.
+    #[default(0)]
+    seed: i64,
+) -> Generator {
+    Generator(seed as u64)
+}
+
+/// A seeded pseudo-random number generator, created with
+/// [`random.gen`]($random.gen).
+///
+/// This is a SplitMix64 generator. Consecutive seeds yield unrelated streams,
+/// so seeding with a loop index is fine.
+#[ty(scope)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Generator(u64);
+
+#[scope]
+impl Generator {
+    /// Generates a float between zero (inclusive) and one (exclusive).
+    ///
+    /// Returns the float and the generator to draw from next.
+    #[func]
+    pub fn float(self) -> Array {
+        let (bits, next) = self.bits();
+        let float = (bits >> 11) as f64 / (1u64 << 53) as f64;
+        array![float, next]
+    }
+
+    /// Generates an integer in a range.
+    ///
+    /// Returns the integer and the generator to draw from next.
+    #[func]
+    pub fn int(
+        self,
+        /// The inclusive start of the range.
+        start: i64,
+        /// The exclusive end of the range.
+        end: Spanned<i64>,
+    ) -> SourceResult<Array> {
+        if end.v <= start {
+            bail!(end.span, "end must be greater than start");
+        }
+        let width = (end.v as i128 - start as i128) as u64;
+        let (offset, next) = self.below(width);
+        Ok(array![(start as i128 + offset as i128) as i64, next])
+    }
+
+    /// Shuffles an array into a random order.
+    ///
+    /// Returns the shuffled array and the generator to draw from next.
+    #[func]
+    pub fn shuffle(
+        self,
+        /// The array to shuffle.
+        array: Array,
+    ) -> Array {
+        let mut generator = self;
+        let mut items: Vec<Value> = array.into_iter().collect();
+        for i in (1..items.len()).rev() {
+            let (j, next) = generator.below(i as u64 + 1);
+            items.swap(i, j as usize);
+            generator = next;
+        }
+        array![items.into_iter().collect::<Array>(), generator]
+    }
+}
+
+impl Generator {
+    /// Produce 64 random bits and the generator that follows.
+    fn bits(self) -> (u64, Self) {
+        let state = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (z ^ (z >> 31), Self(state))
+    }
+
+    /// Produce an integer in `[0, n)` and the generator that follows.
+    fn below(self, n: u64) -> (u64, Self) {
+        let (bits, next) = self.bits();
+        (((bits as u128 * n as u128) >> 64) as u64, next)
+    }
+}
+
+impl Repr for Generator {
+    fn repr(&self) -> EcoString {
+        "generator(..)".into()
+    }
+}
@@ -7,8 +7,9 @@ use smallvec::SmallVec;
 
 use crate::diag::{bail, HintedStrResult, StrResult};
 use crate::foundations::{
-    cast, func, repr, scope, ty, CastInfo, Content, Context, Dict, Element, FromValue,
-    Func, Label, Reflect, Regex, Repr, Str, StyleChain, Type, Value,
+    cast, func, repr, scope, ty, CastInfo, Content, Context, CustomElem, Dict, Element,
+    FromValue, Func, Label, NativeElement, Reflect, Regex, Repr, Str, StyleChain, Type,
+    Value,
 };
 use crate::introspection::{Introspector, Locatable, Location};
 use crate::symbols::Symbol;
@@ -127,7 +128,7 @@ impl Selector {
         match self {
             Self::Elem(element, dict) => {
                 // TODO: Optimize field access to not clone.
-                target.func() == *element
+                target.elem() == *element
                     && dict.iter().flat_map(|dict| dict.iter()).all(|(id, value)| {
                         target.get(*id, styles).as_ref() == Some(value)
                     })
@@ -136,7 +137,7 @@ impl Selector {
             Self::Regex(regex) => target
                 .to_packed::<TextElem>()
                 .is_some_and(|elem| regex.is_match(elem.text())),
-            Self::Can(cap) => target.func().can_type_id(*cap),
+            Self::Can(cap) => target.elem().can_type_id(*cap),
             Self::Or(selectors) => {
                 selectors.iter().any(move |sel| sel.matches(target, styles))
             }
@@ -238,6 +239,13 @@ impl From<Location> for Selector {
 impl Repr for Selector {
     fn repr(&self) -> EcoString {
         match self {
+            Self::Elem(elem, Some(dict)) if *elem == CustomElem::elem() => dict
+                .iter()
+                .find_map(|(_, value)| match value {
+                    Value::Func(func) => func.custom(),
+                    _ => None,
+                })
+                .map_or_else(|| elem.name().into(), |custom| custom.repr_selector(dict)),
             Self::Elem(elem, dict) => {
                 if let Some(dict) = dict {
                     let dict = dict
@@ -278,10 +286,13 @@ impl Repr for Selector {
 
 cast! {
     type Selector,
-    func: Func => func
-        .element()
-        .ok_or("only element functions can be used as selectors")?
-        .select(),
+    func: Func => match func.custom() {
+        Some(custom) => custom.select(),
+        None => func
+            .element()
+            .ok_or("only element functions can be used as selectors")?
+            .select(),
+    },
     label: Label => Self::Label(label),
     text: EcoString => Self::text(&text)?,
     regex: Regex => Self::regex(regex)?,
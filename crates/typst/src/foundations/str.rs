@@ -68,6 +68,32 @@ pub use ecow::eco_format;
 /// - `[\r]` for a carriage return
 /// - `[\t]` for a tab
 /// - `[\u{1f600}]` for a hexadecimal Unicode escape sequence
+/// - `[\u{name:EM DASH}]` for a Unicode escape sequence with a character name
+///
+/// To write a string without escape sequences, prefix it with an `r`. Such a
+/// raw string ends at the next quote and keeps all backslashes as is, which is
+/// useful for regular expressions and Windows paths. In markup, wrap it in
+/// parentheses: `[#r"..."]` embeds a variable named `r` followed by a quote.
+///
+/// ```example
+/// #(r"C:\Users\typst") \
+/// #"a1b22".matches(regex(r"\d+")).len()
+/// ```
+///
+/// # Interpolation { #interpolation }
+/// A string prefixed with an `f` can embed code in braces. The code is
+/// evaluated and its result is inserted into the string. The result can be
+/// anything that can be [converted to a string]($str/#constructor), or
+/// `{none}` to insert nothing. Within such a string, write `[\{]` and `[\}]` for
+/// literal braces. Strings without the prefix keep their braces as is. Like
+/// raw strings, they need parentheses when embedded in markup.
+///
+/// ```example
+/// #let name = "World"
+/// #(f"Hello {name}!") \
+/// #(f"1 + 2 = {1 + 2}") \
+/// #(f"\{not code\}")
+/// ```
 #[ty(scope, cast, title = "String")]
 #[derive(Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[derive(Serialize, Deserialize)]
@@ -868,8 +894,9 @@ impl Regex {
         /// Typst (e.g. `[\\]`), you need to escape twice. Thus, to match a verbatim
         /// backslash, you would need to write `{regex("\\\\")}`.
         ///
-        /// If you need many escape sequences, you can also create a raw element
-        /// and extract its text to use it for your regular expressions:
+        /// If you need many escape sequences, you can also use a
+        /// [raw string]($str/#escapes) (`{regex(r"\d+\.\d+")}`) or create a raw
+        /// element and extract its text to use it for your regular expressions:
         /// ```{regex(`\d+\.\d+\.\d+`.text)}```.
         regex: Spanned<Str>,
     ) -> SourceResult<Regex> {
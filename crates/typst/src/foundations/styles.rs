@@ -390,7 +390,7 @@ impl Recipe {
             Transformation::Func(func) => {
                 let mut result = func.call(engine, context, [content.clone()]);
                 if self.selector.is_some() {
-                    let point = || Tracepoint::Show(content.func().name().into());
+                    let point = || Tracepoint::Show(content.elem().name().into());
                     result = result.trace(engine.world, point, content.span());
                 }
                 result?.display()
@@ -601,8 +601,8 @@ cast! {
     },
     v: Str => Self::Str(v),
     v: Label => Self::Selector(Selector::Label(v)),
-    v: Element => {
-        if v == PageElem::elem() {
+    v: Func => {
+        if v.element() == Some(PageElem::elem()) {
             Self::Page
         } else {
             Self::Selector(LocatableSelector::from_value(v.into_value())?.0)
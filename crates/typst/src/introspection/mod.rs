@@ -104,7 +104,7 @@ impl Debug for Meta {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Self::Link(dest) => write!(f, "Link({dest:?})"),
-            Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
+            Self::Elem(content) => write!(f, "Elem({:?})", content.elem()),
             Self::Hide => f.pad("Hide"),
         }
     }
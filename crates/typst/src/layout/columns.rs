@@ -14,9 +14,10 @@ use crate::util::Numeric;
 /// Separates a region into multiple equally sized columns.
 ///
 /// The `column` function allows to separate the interior of any container into
-/// multiple columns. It will not equalize the height of the columns, instead,
-/// the columns will take up the height of their container or the remaining
-/// height on the page. The columns function can break across pages if
+/// multiple columns. By default, it will not equalize the height of the
+/// columns, instead, the columns will take up the height of their container or
+/// the remaining height on the page. Use the [`balance`]($columns.balance)
+/// parameter to equalize them. The columns function can break across pages if
 /// necessary.
 ///
 /// If you need to insert columns across your whole document, you can use the
@@ -54,6 +55,23 @@ pub struct ColumnsElem {
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// Whether to balance the heights of the columns in the last region.
+    ///
+    /// By default, each column is filled completely before content flows into
+    /// the next one, which can leave the last columns on a page short or
+    /// empty. When balancing, the columns of the last region are shortened to
+    /// the smallest height that still fits the remaining content.
+    ///
+    /// ```example
+    /// #box(height: 68pt, columns(3, balance: true)[
+    ///   This research was funded by
+    ///   the National Academy of
+    ///   Sciences.
+    /// ])
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
@@ -80,24 +98,20 @@ impl LayoutMultiple for Packed<ColumnsElem> {
         let gutter = self.gutter(styles).relative_to(regions.base().x);
         let width = (regions.size.x - gutter * (columns - 1) as f64) / columns as f64;
 
-        let backlog: Vec<_> = std::iter::once(&regions.size.y)
+        // The height of each column, one region after another.
+        let mut heights: Vec<_> = std::iter::once(&regions.size.y)
             .chain(regions.backlog)
             .flat_map(|&height| std::iter::repeat(height).take(columns))
-            .skip(1)
             .collect();
 
-        // Create the pod regions.
-        let pod = Regions {
-            size: Size::new(width, regions.size.y),
-            full: regions.full,
-            backlog: &backlog,
-            last: regions.last,
-            expand: Axes::new(true, regions.expand.y),
-            root: regions.root,
-        };
+        if self.balance(styles) && columns > 1 {
+            balance(engine, styles, body, regions, width, columns, &mut heights)?;
+        }
 
         // Layout the children.
-        let mut frames = body.layout(engine, styles, pod)?.into_iter();
+        let mut frames = body
+            .layout(engine, styles, pod(regions, width, &heights))?
+            .into_iter();
         let mut finished = vec![];
 
         let dir = TextElem::dir_in(styles);
@@ -137,6 +151,74 @@ impl LayoutMultiple for Packed<ColumnsElem> {
     }
 }
 
+/// How often to halve the search interval at most when balancing columns.
+///
+/// The search stops early once the height is found up to a rounding error.
+/// It must be that precise because a larger surplus would let breakable
+/// content start at the very bottom of a column.
+const BALANCE_ITERATIONS: usize = 40;
+
+/// Create the regions for the individual columns.
+fn pod<'a>(regions: Regions, width: Abs, heights: &'a [Abs]) -> Regions<'a> {
+    Regions {
+        size: Size::new(width, heights[0]),
+        full: regions.full,
+        backlog: &heights[1..],
+        last: regions.last,
+        expand: Axes::new(true, regions.expand.y),
+        root: regions.root,
+    }
+}
+
+/// Shorten the columns of the last region to the smallest height at which the
+/// content does not yet overflow into another region.
+///
+/// The search only measures the body, the caller lays it out afterwards.
+fn balance(
+    engine: &mut Engine,
+    styles: StyleChain,
+    body: &Content,
+    regions: Regions,
+    width: Abs,
+    columns: usize,
+    heights: &mut Vec<Abs>,
+) -> SourceResult<()> {
+    let count = body.measure(engine, styles, pod(regions, width, heights))?.len();
+    let total = count.div_ceil(columns);
+    let Some(last) = total.checked_sub(1) else { return Ok(()) };
+    let Some(full) = regions.iter().nth(last).map(|size| size.y) else {
+        return Ok(());
+    };
+
+    if !full.is_finite() {
+        return Ok(());
+    }
+
+    let start = last * columns;
+    if heights.len() < start + columns {
+        heights.resize(start + columns, full);
+    }
+
+    let (mut lo, mut hi) = (Abs::zero(), full);
+    for _ in 0..BALANCE_ITERATIONS {
+        if lo.approx_eq(hi) {
+            break;
+        }
+
+        let mid = (lo + hi) / 2.0;
+        heights[start..start + columns].fill(mid);
+        let pod = pod(regions, width, heights);
+        if body.measure(engine, styles, pod)?.len() <= total * columns {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    heights[start..start + columns].fill(hi);
+    Ok(())
+}
+
 /// Forces a column break.
 ///
 /// The function will behave like a [page break]($pagebreak) when used in a
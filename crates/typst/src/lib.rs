@@ -65,7 +65,7 @@ use typst_timing::{timed, TimingScope};
 
 use crate::diag::{warning, FileResult, SourceDiagnostic, SourceResult};
 use crate::engine::{Engine, Route};
-use crate::eval::Tracer;
+use crate::eval::{Limits, Tracer};
 use crate::foundations::{
     Array, Bytes, Content, Datetime, Dict, Module, Scope, StyleChain, Styles,
 };
@@ -255,6 +255,8 @@ pub struct Library {
     /// The default style properties (for page size, font selection, and
     /// everything else configurable via set and show rules).
     pub styles: Styles,
+    /// The limits on the work that evaluation may do.
+    pub(crate) limits: Limits,
 }
 
 impl Library {
@@ -277,6 +279,7 @@ impl Default for Library {
 #[derive(Debug, Clone, Default)]
 pub struct LibraryBuilder {
     inputs: Option<Dict>,
+    limits: Limits,
 }
 
 impl LibraryBuilder {
@@ -286,12 +289,23 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure the limits on the work that evaluation may do.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
         let inputs = self.inputs.unwrap_or_default();
         let global = global(math.clone(), inputs);
-        Library { global, math, styles: Styles::new() }
+        Library {
+            global,
+            math,
+            styles: Styles::new(),
+            limits: self.limits,
+        }
     }
 }
 
@@ -237,7 +237,7 @@ impl Synthesize for Packed<FigureElem> {
         let kind = elem.kind(styles).unwrap_or_else(|| {
             elem.body()
                 .query_first(Selector::can::<dyn Figurable>())
-                .map(|elem| FigureKind::Elem(elem.func()))
+                .map(|elem| FigureKind::Elem(elem.elem()))
                 .unwrap_or_else(|| FigureKind::Elem(ImageElem::elem()))
         });
 
@@ -49,6 +49,14 @@ use crate::visualize::{LineElem, Stroke};
 /// _Note:_ Set and show rules in the scope where `footnote` is called may not
 /// apply to the footnote's content. See [here][issue] for more information.
 ///
+/// # Syntax
+/// This function also has dedicated syntax: A caret directly followed by a
+/// content block creates a footnote with that content.
+///
+/// ```example
+/// Typst is written in Rust.^[https://www.rust-lang.org]
+/// ```
+///
 /// [issue]: https://github.com/typst/typst/issues/1467#issuecomment-1588799440
 #[elem(scope, Locatable, Show, Count)]
 pub struct FootnoteElem {
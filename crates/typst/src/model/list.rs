@@ -44,6 +44,15 @@ use crate::text::TextElem;
 /// followed by a space to create a list item. A list item can contain multiple
 /// paragraphs and other block-level content. All content that is indented
 /// more than an item's marker becomes part of that item.
+///
+/// A list item can start with a checkbox to create a task list: `[ ]` for an
+/// open task and `[x]` for a completed one. Items with a checkbox show it in
+/// place of the list's marker.
+///
+/// ```example
+/// - [x] Write the draft
+/// - [ ] Proofread
+/// ```
 #[elem(scope, title = "Bullet List", LayoutMultiple)]
 pub struct ListElem {
     /// If this is `{false}`, the items are spaced apart with
@@ -163,8 +172,13 @@ impl LayoutMultiple for Packed<ListElem> {
 
         let mut cells = vec![];
         for item in self.children() {
+            let marker = match item.checked(styles) {
+                Some(checked) => checkbox(checked),
+                None => marker.clone(),
+            };
+
             cells.push(Cell::from(Content::empty()));
-            cells.push(Cell::from(marker.clone()));
+            cells.push(Cell::from(marker));
             cells.push(Cell::from(Content::empty()));
             cells.push(Cell::from(
                 item.body().clone().styled(ListElem::set_depth(Depth(1))),
@@ -187,12 +201,29 @@ impl LayoutMultiple for Packed<ListElem> {
     }
 }
 
+/// Creates the checkbox that replaces the marker of a task list item.
+fn checkbox(checked: bool) -> Content {
+    TextElem::packed(if checked { '\u{2611}' } else { '\u{2610}' })
+        .aligned(HAlignment::Start + VAlignment::Top)
+}
+
 /// A bullet list item.
 #[elem(name = "item", title = "Bullet List Item")]
 pub struct ListItem {
     /// The item's body.
     #[required]
     pub body: Content,
+
+    /// Whether the item's checkbox is checked. If this is `{none}`, the item
+    /// has no checkbox and is introduced by the list's marker instead.
+    ///
+    /// ```example
+    /// #list(
+    ///   list.item(checked: true)[Done],
+    ///   list.item(checked: false)[Todo],
+    /// )
+    /// ```
+    pub checked: Option<bool>,
 }
 
 cast! {
@@ -494,7 +494,7 @@ impl OutlineEntry {
         styles: StyleChain,
     ) -> SourceResult<Option<Self>> {
         let Some(outlinable) = elem.with::<dyn Outlinable>() else {
-            bail!(span, "cannot outline {}", elem.func().name());
+            bail!(span, "cannot outline {}", elem.elem().name());
         };
 
         let Some(body) = outlinable.outline(engine, styles)? else {
@@ -529,11 +529,11 @@ impl Show for Packed<OutlineEntry> {
         let Some(location) = elem.location() else {
             if elem.can::<dyn Locatable>() && elem.can::<dyn Outlinable>() {
                 bail!(
-                    self.span(), "{} must have a location", elem.func().name();
+                    self.span(), "{} must have a location", elem.elem().name();
                     hint: "try using a query or a show rule to customize the outline.entry instead",
                 )
             } else {
-                bail!(self.span(), "cannot outline {}", elem.func().name())
+                bail!(self.span(), "cannot outline {}", elem.elem().name())
             }
         };
 
@@ -177,7 +177,7 @@ impl Show for Packed<RefElem> {
 
         let elem = elem.at(span)?;
 
-        if elem.func() == FootnoteElem::elem() {
+        if elem.elem() == FootnoteElem::elem() {
             return Ok(FootnoteElem::with_label(target).pack().spanned(span));
         }
 
@@ -188,10 +188,10 @@ impl Show for Packed<RefElem> {
                 if elem.can::<dyn Figurable>() {
                     eco_format!(
                         "cannot reference {} directly, try putting it into a figure",
-                        elem.func().name()
+                        elem.elem().name()
                     )
                 } else {
-                    eco_format!("cannot reference {}", elem.func().name())
+                    eco_format!("cannot reference {}", elem.elem().name())
                 }
             })
             .at(span)?;
@@ -199,15 +199,15 @@ impl Show for Packed<RefElem> {
         let numbering = refable
             .numbering()
             .ok_or_else(|| {
-                eco_format!("cannot reference {} without numbering", elem.func().name())
+                eco_format!("cannot reference {} without numbering", elem.elem().name())
             })
             .hint(eco_format!(
                 "you can enable {} numbering with `#set {}(numbering: \"1.\")`",
-                elem.func().name(),
-                if elem.func() == EquationElem::elem() {
+                elem.elem().name(),
+                if elem.elem() == EquationElem::elem() {
                     "math.equation"
                 } else {
-                    elem.func().name()
+                    elem.elem().name()
                 }
             ))
             .at(span)?;
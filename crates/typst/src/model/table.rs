@@ -120,6 +120,22 @@ use crate::visualize::{Paint, Stroke};
 ///   [Robert], b, a, b,
 /// )
 /// ```
+///
+/// # Syntax
+/// This function also has dedicated syntax: A table starts with a header row,
+/// whose cells are enclosed in pipes, followed by a separator line with a run
+/// of dashes for each column. Each further line that starts with a pipe adds a
+/// row. The header row becomes a [`table.header`]($table.header) and the
+/// columns are sized automatically. Colons in the separator line align a
+/// column to the left (`:--`), center (`:-:`), or right (`--:`). Outside of
+/// such a table, pipes are just text.
+///
+/// ```example
+/// | *Name* | *Age* |
+/// |--------|----:|
+/// | Hannes | 36 |
+/// | Irma | 50 |
+/// ```
 #[elem(scope, LayoutMultiple, LocalName, Figurable)]
 pub struct TableElem {
     /// The column sizes. See the [grid documentation]($grid) for more
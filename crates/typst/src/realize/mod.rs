@@ -174,7 +174,7 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
         if content.is::<PagebreakElem>() {
             bail!(content.span(), "pagebreaks are not allowed inside of containers");
         } else {
-            bail!(content.span(), "{} is not allowed here", content.func().name());
+            bail!(content.span(), "{} is not allowed here", content.elem().name());
         }
     }
 
@@ -472,7 +472,7 @@ impl<'a> ListBuilder<'a> {
                 .items
                 .items()
                 .next()
-                .map_or(true, |first| first.func() == content.func())
+                .map_or(true, |first| first.elem() == content.elem())
         {
             self.items.push(content, styles);
             self.tight &= self.staged.drain(..).all(|(t, _)| !t.is::<ParbreakElem>());
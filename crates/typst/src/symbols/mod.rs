@@ -15,6 +15,13 @@ use crate::foundations::{category, Category, Scope};
 /// enter Unicode symbols into your text and formulas. In addition to the
 /// symbols listed below, math mode defines `dif` and `Dif`. These are not
 /// normal symbol values because they also affect spacing and font style.
+///
+/// In markup, emoji can also be written as shortcodes: `[:face.grin:]` is the
+/// same as `[#emoji.face.grin]`. Shortcodes are looked up in the `emoji`
+/// binding in scope first, so you can add your own by shadowing it with a
+/// dictionary, e.g. `{let emoji = (party: "🎉")}`. Names that are not found
+/// there fall back to the built-in emoji. Unknown shortcodes are kept as text
+/// with a warning.
 #[category]
 pub static SYMBOLS: Category;
 
@@ -198,6 +198,13 @@ impl Show for Packed<OverlineElem> {
 /// ```example
 /// This is #strike[not] relevant.
 /// ```
+///
+/// # Syntax
+/// This function also has dedicated syntax: To strike through content, enclose
+/// it in double tildes (`~~`). The opening tildes must not directly follow a
+/// word and the closing ones must not directly precede one, so `a~~b` is
+/// still two non-breaking spaces. The same goes for tildes without a partner
+/// in the same paragraph, a single tilde, and longer runs of tildes.
 #[elem(title = "Strikethrough", Show)]
 pub struct StrikeElem {
     /// How to [stroke] the line.
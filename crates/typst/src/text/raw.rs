@@ -9,18 +9,18 @@ use syntect::highlighting as synt;
 use syntect::parsing::{SyntaxDefinition, SyntaxSet, SyntaxSetBuilder};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::diag::{At, FileError, SourceResult, StrResult};
+use crate::diag::{bail, At, FileError, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, scope, Args, Array, Bytes, Content, Fold, NativeElement, Packed,
+    array, cast, elem, scope, Args, Array, Bytes, Content, Fold, NativeElement, Packed,
     PlainText, Show, ShowSet, Smart, StyleChain, Styles, Synthesize, Value,
 };
 use crate::layout::{BlockElem, Em, HAlignment};
 use crate::model::{Figurable, ParElem};
 use crate::syntax::{split_newlines, LinkedNode, Span, Spanned};
 use crate::text::{
-    FontFamily, FontList, Hyphenate, Lang, LinebreakElem, LocalName, Region,
-    SmartQuoteElem, TextElem, TextSize,
+    FontFamily, FontList, HighlightElem, Hyphenate, Lang, LinebreakElem, LocalName,
+    Region, SmartQuoteElem, TextElem, TextSize,
 };
 use crate::util::option_eq;
 use crate::visualize::Color;
@@ -70,6 +70,12 @@ type LineFn<'a> = &'a mut dyn FnMut(usize, Range<usize>, &mut Vec<Content>);
 /// needed, start the text with a single space (which will be trimmed) or use
 /// the single backtick syntax. If your text should start or end with a
 /// backtick, put a space before or after it (it will be trimmed).
+///
+/// The language tag can be followed by options without any spaces in between:
+/// A colon and a range of lines to display (`rust:3..10`) and/or a list of
+/// lines to highlight in curly braces (`rust{5,7..8}`). These set the
+/// [`line-range`]($raw.line-range) and
+/// [`highlight-lines`]($raw.highlight-lines) properties, respectively.
 #[elem(
     scope,
     title = "Raw Text / Code",
@@ -257,6 +263,36 @@ pub struct RawElem {
     #[default(2)]
     pub tab_size: usize,
 
+    /// The range of lines to display, given as an array of the first and the
+    /// last line number. Lines are counted from 1 and the other lines are
+    /// hidden, but keep their line numbers. The range must not be inverted and
+    /// must lie within the lines of the raw text.
+    ///
+    /// ````example
+    /// ```rust:2..3
+    /// fn main() {
+    ///     println!("Hello World!");
+    /// }
+    /// ```
+    /// ````
+    pub line_range: Option<RawLineRange>,
+
+    /// The numbers of the lines to highlight, counted from 1. Each number must
+    /// belong to a line of the raw text.
+    ///
+    /// A highlighted line has its [`highlighted`]($raw.line.highlighted)
+    /// property set, which shows it with a [highlight]($highlight) by default.
+    ///
+    /// ````example
+    /// #raw(
+    ///   "let x = 1\nlet y = 2",
+    ///   lang: "rust",
+    ///   block: true,
+    ///   highlight-lines: (2,),
+    /// )
+    /// ````
+    pub highlight_lines: Vec<i64>,
+
     /// The stylized lines of raw text.
     ///
     /// Made accessible for the [`raw.line` element]($raw.line).
@@ -290,7 +326,33 @@ impl RawElem {
 
 impl Synthesize for Packed<RawElem> {
     fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
-        let seq = self.highlight(styles);
+        let mut seq = self.highlight(styles);
+        let count = seq.len() as i64;
+
+        if let Some(range) = self.line_range(styles) {
+            if range.end > count {
+                bail!(
+                    self.span(), "line range ends after the last line";
+                    hint: "the raw text has {count} line(s)"
+                );
+            }
+            seq.retain(|line| range.contains(*line.number()));
+        }
+
+        let highlighted = self.highlight_lines(styles);
+        if let Some(number) = highlighted.iter().find(|&&n| n < 1 || n > count) {
+            bail!(
+                self.span(), "cannot highlight line {number}";
+                hint: "the raw text has {count} line(s)"
+            );
+        }
+
+        for line in &mut seq {
+            if highlighted.contains(line.number()) {
+                line.push_highlighted(true);
+            }
+        }
+
         self.push_lines(seq);
         Ok(())
     }
@@ -530,6 +592,45 @@ cast! {
     v: EcoString => Self::Text(v),
 }
 
+/// A range of line numbers in raw text, inclusive on both ends.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RawLineRange {
+    /// The first line to display.
+    pub start: i64,
+    /// The last line to display.
+    pub end: i64,
+}
+
+impl RawLineRange {
+    /// Create a range of line numbers, checking that it is not inverted.
+    pub fn new(start: i64, end: i64) -> StrResult<Self> {
+        if start < 1 {
+            bail!("line numbers must be at least 1");
+        }
+        if start > end {
+            bail!("line range must not end before it starts");
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Whether the line with the given number lies within the range.
+    pub fn contains(&self, number: i64) -> bool {
+        (self.start..=self.end).contains(&number)
+    }
+}
+
+cast! {
+    RawLineRange,
+    self => array![self.start, self.end].into_value(),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), None) => Self::new(a.cast()?, b.cast()?)?,
+            _ => bail!("array must contain exactly two entries"),
+        }
+    },
+}
+
 /// A highlighted line of raw text.
 ///
 /// This is a helper element that is synthesized by [`raw`] elements.
@@ -554,12 +655,33 @@ pub struct RawLine {
     /// The highlighted raw text.
     #[required]
     pub body: Content,
+
+    /// Whether the line is highlighted through the raw block's
+    /// [`highlight-lines`]($raw.highlight-lines) property.
+    ///
+    /// ````example
+    /// #show raw.line: it => {
+    ///   if it.highlighted [▶ ]
+    ///   it.body
+    /// }
+    ///
+    /// ```rust{2}
+    /// let x = 1;
+    /// let y = 2;
+    /// ```
+    /// ````
+    #[default(false)]
+    pub highlighted: bool,
 }
 
 impl Show for Packed<RawLine> {
     #[typst_macros::time(name = "raw.line", span = self.span())]
-    fn show(&self, _: &mut Engine, _styles: StyleChain) -> SourceResult<Content> {
-        Ok(self.body().clone())
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let body = self.body().clone();
+        if self.highlighted(styles) {
+            return Ok(HighlightElem::new(body).pack().spanned(self.span()));
+        }
+        Ok(body)
     }
 }
 
@@ -20,6 +20,17 @@ use crate::visualize::{Geometry, Stroke};
 ///   stroke: 2pt + maroon,
 /// )
 /// ```
+///
+/// # Syntax
+/// This function also has dedicated syntax: Three or more dashes alone on their
+/// line create a horizontal rule, that is, a line spanning the full width.
+/// Style them with set and show rules for `line`.
+///
+/// ```example
+/// Above
+/// ---
+/// Below
+/// ```
 #[elem(LayoutSingle)]
 pub struct LineElem {
     /// The start point of the line.
@@ -31,7 +31,7 @@ use oxipng::{InFile, Options, OutFile};
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use tiny_skia as sk;
 use typst::diag::{bail, FileError, FileResult, Severity, SourceDiagnostic, StrResult};
-use typst::eval::Tracer;
+use typst::eval::{Limits, Tracer};
 use typst::foundations::{func, Bytes, Datetime, NoneValue, Repr, Smart, Value};
 use typst::introspection::Meta;
 use typst::layout::{Abs, Frame, FrameItem, Margin, Page, PageElem, Transform};
@@ -193,6 +193,15 @@ fn main() {
     }
 }
 
+/// Evaluation limits that are generous enough for all regular tests.
+fn limits() -> Limits {
+    Limits {
+        iterations: Some(100_000),
+        calls: Some(100_000),
+        steps: Some(1_000_000),
+    }
+}
+
 fn library() -> Library {
     #[func]
     fn test(lhs: Value, rhs: Value) -> StrResult<NoneValue> {
@@ -227,7 +236,7 @@ fn library() -> Library {
     // Set page width to 120pt with 10pt margins, so that the inner page is
     // exactly 100pt wide. Page height is unbounded and font size is 10pt so
     // that it multiplies to nice round numbers.
-    let mut lib = Library::default();
+    let mut lib = Library::builder().with_limits(limits()).build();
     lib.styles
         .set(PageElem::set_width(Smart::Custom(Abs::pt(120.0).into())));
     lib.styles.set(PageElem::set_height(Smart::Auto));
@@ -0,0 +1,88 @@
+// Test user-defined elements.
+
+---
+// Test construction and field access.
+// Ref: false
+#let note = element("note", it => [Note: #it.body], "body", urgent: false)
+#let n = note[Hi]
+#test(n.body, [Hi])
+#test(n.has("urgent"), false)
+#test(n.func(), note)
+#test(n.fields(), (body: [Hi]))
+#test(note(urgent: true, body: [A]).urgent, true)
+#test(repr(note), "note")
+#test(repr(n), "note(body: [Hi])")
+#test(repr(note.where(urgent: true)), "note.where(urgent: true)")
+
+---
+// Test that set rules and defaults are resolved before display.
+#let note = element(
+  "note",
+  it => text(fill: if it.urgent { red } else { black })[#it.kind: #it.body],
+  "body",
+  urgent: false,
+  kind: [Note],
+)
+
+#note[Plain]
+#set note(kind: [Hint])
+#note[Hinted]
+#[
+  #set note(urgent: true)
+  #note[Urgent]
+]
+#note(urgent: true, kind: [Warning])[Explicit]
+
+---
+// Test show, show-set and where rules.
+#let note = element("note", it => it.body, "body", urgent: false)
+#show note: set text(blue)
+#show note.where(urgent: true): it => strong(it)
+#note[Blue]
+#note(urgent: true)[Strong]
+
+#show note: it => [(#it.body)]
+#note[Parenthesized]
+
+---
+// Test that elements of different kinds do not mix.
+// Ref: false
+#let a = element("a", it => [A], x: 1)
+#let b = element("b", it => [B], x: 1)
+#set a(x: 2)
+#show a: it => test(it.x, 2)
+#show b: it => test(it.x, 1)
+#a() #b()
+#test(a == b, false)
+
+---
+// Test query and counters.
+// Ref: false
+#let note = element("note", it => it.body, "body")
+#note[A] #note[B] <b> #note[C]
+#context test(query(note).map(it => it.body), ([A], [B], [C]))
+#context test(query(<b>).first().func(), note)
+#context test(counter(note).final(), (3,))
+
+---
+#let note = element("note", it => it.body, "body")
+// Error: 2-8 missing argument: body
+#note()
+
+---
+#let note = element("note", it => it.body, "body", urgent: false)
+// Error: 11-20 unexpected argument: body
+#set note(body: [A])
+
+---
+#let note = element("note", it => it.body, "body")
+// Error: 2-25 element `note` does not have field `kind`
+#note.where(kind: "tip")
+
+---
+// Error: 2-31 cannot declare field `label`
+#element("x", x => x, "label")
+
+---
+// Error: 2-33 duplicate field: a
+#element("x", x => x, "a", a: 1)
@@ -0,0 +1,23 @@
+// Test the evaluation limits. The test runner configures them in its library.
+// Ref: false
+
+---
+// Error: 2-27 loop exceeded the limit of 100000 iterations
+#for i in range(100001) {}
+
+---
+// Error: 46-48 evaluation exceeded the limit of 1000000 steps
+#for i in range(20) { for j in range(100000) {} }
+
+---
+// Error: 2:47-2:50 evaluation exceeded the limit of 100000 function calls
+#let f() = none
+#for i in range(200) { for j in range(1000) { f() } }
+
+---
+// Steps are counted across function bodies.
+// Error: 4:23-4:31 evaluation exceeded the limit of 1000000 steps
+#let f(n) = {
+  for i in range(600) {}
+}
+#for i in range(2000) { f(i) }
@@ -0,0 +1,48 @@
+// Test string interpolation.
+// Ref: false
+
+---
+#let name = "World"
+#test(f"Hello {name}!", "Hello World!")
+#test(f"{1 + 2} and {2.5}", "3 and 2.5")
+#test(f"{ let x = 2; x * 3 }", "6")
+#test(f"{<intro>} {str}", "intro string")
+#test(f"a{none}b{if false { 1 }}c", "abc")
+#test(f"a{f"b{"c"}d"}e", "abcde")
+#test(f"plain", "plain")
+
+---
+// Interpolated strings can span multiple lines.
+#test(f"a{1}
+b", "a1\nb")
+
+---
+// Braces are escaped in interpolated strings and kept in all others.
+#test(f"\{name\}", "{name}")
+#test(f"\u{41}{1}", "A1")
+#test("{name}".len(), 6)
+#test("\{".len(), 2)
+#test(r"{name}", "{name}")
+#test(repr("{}"), "\"{}\"")
+#test("12:30".match(regex("\d{2}:\d{2}")).text, "12:30")
+
+---
+// Interpolated strings work in markup and as dictionary keys.
+#let key = "b"
+#test((f"a{key}": 1).keys(), ("ab",))
+#test([#(f"x{1}y")], [x1y])
+
+---
+// A variable named `f` followed by a quote in markup is not an interpolated
+// string.
+#let f = "x"
+#test([#f"a{1}"], [x"a{1}"])
+
+---
+// Error: 6-11 expected integer, float, version, bytes, label, type, or string, found content
+#(f"a{[b]}")
+
+---
+// Error: 6-7 unclosed delimiter
+// Error: 7-9 unclosed string
+#func(f"a{1}b
@@ -0,0 +1,25 @@
+// Test raw strings.
+// Ref: false
+
+---
+#test(r"C:\Users\n", "C:\\Users\\n")
+#test(r"", "")
+#test(r"\d+", "\\d+")
+#test("a12b3".matches(regex(r"\d+")).map(m => m.text), ("12", "3"))
+
+---
+// Raw strings can span multiple lines.
+#test(r"a
+b", "a\nb")
+
+---
+// A variable named `r` followed by a quote in markup is not a raw string.
+#let r = "x"
+#test([#r"a"], [x"a"])
+#let d = (r: "y")
+#test([#d.r"a"], [y"a"])
+
+---
+// Error: 6-7 unclosed delimiter
+// Error: 1:7-2:1 unclosed string
+#func(r"abc
@@ -0,0 +1,64 @@
+// Test seeded pseudo-random numbers.
+// Ref: false
+
+---
+// Test that a fixed seed always yields the same stream.
+#let rng = random.gen(42)
+#let (x, rng) = rng.float()
+#test(x, 0.7415648787718233)
+#let (x, rng) = rng.float()
+#test(x, 0.1599103928769201)
+#let (n, rng) = rng.int(1, 7)
+#test(n, 2)
+#let (n, rng) = rng.int(1, 7)
+#test(n, 3)
+#let (items, rng) = rng.shuffle(range(5))
+#test(items, (2, 1, 4, 3, 0))
+
+#let rng = random.gen()
+#let (x, rng) = rng.float()
+#test(x, 0.8833108082136426)
+#let rolls = ()
+#for _ in range(6) {
+  let (n, next) = rng.int(1, 7)
+  rolls.push(n)
+  rng = next
+}
+#test(rolls, (3, 1, 6, 1, 2, 2))
+
+---
+// Test that drawing doesn't change the generator it is called on.
+#let rng = random.gen(1)
+#test(rng.float(), rng.float())
+#let (a, next) = rng.float()
+#test(next.float().first() == a, false)
+#test(random.gen(1).float(), rng.float())
+#test(random.gen(1) != random.gen(2), true)
+#test(random.gen().float().first(), 0.8833108082136426)
+#test(type(rng), random.generator)
+#test(repr(rng), "generator(..)")
+
+---
+// Test generated numbers are in range.
+#let rng = random.gen(3)
+#for i in range(100) {
+  let (x, next) = rng.float()
+  test(x >= 0 and x < 1, true)
+  let (n, next) = next.int(-3, 4)
+  test(n >= -3 and n < 4, true)
+  rng = next
+}
+#test(rng.int(5, 6).first(), 5)
+#test(type(rng.int(0, calc.pow(2, 62)).first()), int)
+
+---
+// Test that shuffling permutes the array.
+#let rng = random.gen(3)
+#let items = range(20)
+#test(rng.shuffle(items).first().sorted(), items)
+#test(rng.shuffle(()).first(), ())
+#test(rng.shuffle((1,)).first(), (1,))
+
+---
+// Error: 38-39 end must be greater than start
+#let rng = random.gen(); #rng.int(5, 5)
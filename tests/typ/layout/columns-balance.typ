@@ -0,0 +1,67 @@
+// Test balanced columns.
+
+---
+// Test balancing within a single region.
+#set page(width: 160pt, height: 120pt)
+#columns(3, balance: true, lorem(20))
+
+---
+// Test that balancing splits sized blocks evenly and that the content after
+// the columns follows the shortened columns.
+#set page(height: 120pt)
+#set block(spacing: 0pt)
+#columns(2, balance: true)[
+  #block(width: 100%, height: 20pt, fill: conifer)
+  #block(width: 100%, height: 20pt, fill: eastern)
+  #block(width: 100%, height: 20pt, fill: conifer) <third>
+  #block(width: 100%, height: 20pt, fill: eastern)
+]
+#context test(locate(<third>).position().y, 10pt)
+#context test(locate(<third>).position().x > 50pt, true)
+#context test(here().position().y < 60pt, true)
+
+---
+// Test content taller than one region. Only the columns of the last region
+// are balanced.
+#set page(height: 60pt)
+#set block(spacing: 0pt)
+#columns(2, balance: true)[
+  #block(width: 100%, height: 20pt, fill: conifer)
+  #block(width: 100%, height: 20pt, fill: eastern)
+  #block(width: 100%, height: 20pt, fill: conifer) <third>
+  #block(width: 100%, height: 20pt, fill: eastern)
+  #block(width: 100%, height: 20pt, fill: conifer) <fifth>
+  #block(width: 100%, height: 20pt, fill: eastern) <sixth>
+]
+#context test(locate(<third>).position(), (page: 1, x: 62pt, y: 10pt))
+#context test(locate(<fifth>).position(), (page: 2, x: 10pt, y: 10pt))
+#context test(locate(<sixth>).position().page, 2)
+#context test(locate(<sixth>).position().x > 50pt, true)
+#context test(locate(<sixth>).position().y, 10pt)
+
+---
+// Test balancing the last of several regions with page columns.
+#set page(width: 160pt, height: 80pt, columns: 2)
+#set columns(balance: true)
+#lorem(120)
+
+---
+// Test that a single column is filled as usual.
+#set page(height: 120pt)
+#set block(spacing: 0pt)
+#columns(1, balance: true)[
+  #block(width: 100%, height: 20pt, fill: conifer)
+  #block(width: 100%, height: 20pt, fill: eastern) <second>
+]
+#context test(locate(<second>).position().y, 30pt)
+#context test(here().position().y, 50pt)
+
+---
+// Test that empty content takes no space and that an explicit column break
+// is kept.
+#set page(height: 120pt)
+#set block(spacing: 0pt)
+#columns(2, balance: true)[]
+#context test(here().position().y, 10pt)
+#columns(2, balance: true)[A #colbreak() #box[B] <b>]
+#context test(locate(<b>).position().x > 50pt, true)
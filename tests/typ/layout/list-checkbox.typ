@@ -0,0 +1,28 @@
+// Test checkboxes in list items.
+// Ref: false
+
+---
+#let items = [
+  - [ ] Todo
+  - [x] Done
+  - [X] Also done
+  - Plain
+].children.filter(it => it.func() == list.item)
+
+#test(items.map(it => it.at("checked", default: none)), (false, true, true, none))
+#test(items.first().body, [Todo])
+
+---
+// Brackets with other content are not a checkbox.
+#let item = [- [y] Text]
+#test(item.func(), list.item)
+#test(item.at("checked", default: none), none)
+
+---
+// Ref: true
+// Test that checkboxes replace the marker and align with the body.
+- [ ] Todo
+- [x] Done
+- Plain
+  - [X] Nested and done \
+    on two lines
@@ -0,0 +1,49 @@
+// Test tables in markup.
+// Ref: false
+
+---
+#let t = [| a | *b* |
+|---|---|]
+#test(t.func(), table)
+#test(t.columns, (auto, auto))
+#test(t.children.len(), 1)
+#test(t.children.first().func(), table.header)
+#test(t.children.first().children.map(cell => cell.body), ([a], [*b*]))
+
+---
+// Test that body rows join the table and that short rows leave their
+// remaining cells empty.
+#let t = [| a | b | c |
+|---|---|---|
+| d |
+| e | f]
+#test(t.columns.len(), 3)
+#test(
+  t.children.slice(1).map(cell => (cell.x, cell.y)),
+  ((0, 1), (0, 2), (1, 2)),
+)
+
+---
+// Test column alignment in the separator line.
+#let t = [| a | b | c | d |
+| :-- | :-: | --: | --- |
+| e | f | g | h |]
+#test(
+  t.children.slice(1).map(cell => cell.at("align", default: auto)),
+  (left, center, right, auto),
+)
+
+---
+// Test that pipes within nested blocks in a cell are just text.
+#let t = [| #[a|b] | c |
+|---|---|]
+#test(t.children.first().children.first().body, [a|b])
+
+---
+// Test that pipes outside of tables are just text.
+#test([a|b].func(), text)
+#test([a|b].text, "a|b")
+#test([|x|].text, "|x|")
+#test([| a | b |].func() == table, false)
+#test([|---|].func() == table, false)
+#test([x | y].children.map(it => it.func()).contains(table), false)
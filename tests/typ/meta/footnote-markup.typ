@@ -0,0 +1,12 @@
+// Test footnote markup.
+// Ref: false
+
+---
+#let note = [^[A *note*]]
+#test(note.func(), footnote)
+#test(note.body, [A *note*])
+
+---
+// Test that a caret without a content block is just text.
+#test([x^2].func(), text)
+#test([^ alone].children.map(it => it.func()).contains(footnote), false)
@@ -0,0 +1,13 @@
+// Test labels attached to headings in markup.
+// Ref: false
+
+---
+#test([= Intro <intro>].func(), heading)
+#test([= Intro <intro>].label, <intro>)
+#test([= Intro<intro>].label, <intro>)
+#test([== Deep \ Heading <deep>].label, <deep>)
+
+---
+#show <intro>: set text(red)
+= Intro <intro>
+#context test(query(<intro>).first().body, [Intro])
@@ -0,0 +1,20 @@
+// Test labels attached to raw text and equations in markup.
+// Ref: false
+
+---
+#test([$x$ <x>].func(), math.equation)
+#test([$x$ <x>].label, <x>)
+#test([$ x $ <x>].block, true)
+#test([$ x $<x>].label, <x>)
+#test([`a` <a>].func(), raw)
+#test([`a` <a>].label, <a>)
+#test([```rs fn``` <fn>].lang, "rs")
+
+---
+$ a^2 + b^2 = c^2 $ <pythagoras>
+#context test(query(<pythagoras>).first().func(), math.equation)
+
+---
+// A label after a call is still attached to the call's result.
+#figure[A] <fig>
+#context test(query(<fig>).first().func(), figure)
@@ -0,0 +1,41 @@
+// Test strikethrough markup.
+// Ref: false
+
+---
+#let struck = [~~A *b*~~]
+#test(struck.func(), strike)
+#test(struck.body, [A *b*])
+
+---
+// A single tilde and longer runs stay non-breaking spaces.
+#test([a~b].children.map(it => it.func()).contains(strike), false)
+#test([a~~~b].children.map(it => it.func()).contains(strike), false)
+
+---
+// Tildes within words and unpaired tildes stay non-breaking spaces.
+#let struck(it) = it.children.map(it => it.func()).filter(f => f == strike).len()
+#test(struck([a~~b]), 0)
+#test(struck([Gap:~~here]), 0)
+#test(struck([a~~b~~c]), 0)
+#test(struck([x ~~b]), 0)
+#test(struck([~~Not closed]), 0)
+
+// Delimiters only pair up within the same paragraph.
+#test(struck([~~a
+
+b~~]), 0)
+
+// A third delimiter that can only close stays non-breaking spaces.
+#test(struck([~~a~~ b~~]), 1)
+
+---
+// Tildes in strings, raw text and equations don't pair up with markup ones.
+#let struck(it) = it.children.map(it => it.func()).filter(f => f == strike).len()
+#test(struck([x ~~a #"~~" b~~]), 1)
+#test(struck([x ~~a `~~` b~~]), 1)
+#test(struck([x ~~a $x~~y$ b~~]), 1)
+#test([~~a #"~~" b~~].body, [a #"~~" b])
+
+---
+// Unclosed delimiters are kept as non-breaking spaces.
+#test([~~Not closed].children.first(), text("\u{a0}\u{a0}"))
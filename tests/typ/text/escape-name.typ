@@ -0,0 +1,23 @@
+// Test Unicode escape sequences with character names.
+// Ref: false
+
+---
+#test([\u{name:EM DASH}], [—])
+#test([\u{name:greek small letter alpha}], [α])
+#test("\u{name:EM DASH}", "—")
+#test("a\u{name:NO-BREAK SPACE}b", "a\u{a0}b")
+
+---
+// Without the prefix, the sequence is always a hexadecimal codepoint, even if
+// it is also a character name.
+#test(str.to-unicode("\u{BED}"), 0xBED)
+#test(str.to-unicode("\u{name:BED}"), 0x1F6CF)
+#test([\u{41}], [A])
+
+---
+// Error: 1-25 unknown Unicode character name: NOT A CHARACTER
+\u{name:NOT A CHARACTER}
+
+---
+// Error: 1-12 invalid Unicode codepoint: EM DASH
+\u{EM DASH}
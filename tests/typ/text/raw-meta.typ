@@ -0,0 +1,108 @@
+// Test line ranges and highlighted lines in raw blocks.
+// Ref: false
+
+---
+#let numbers = state("numbers", ())
+#show raw.line: it => numbers.update(n => n + ((it.number, it.highlighted),))
+
+```rust:2..3{1,3}
+fn main() {
+    println!("Hello World!");
+}
+```
+
+#context test(numbers.get(), ((2, false), (3, true)))
+
+---
+#let block = ```rust:2..3{2..3}
+a
+b
+c
+```
+#test(block.lang, "rust")
+#test(block.line-range, (2, 3))
+#test(block.highlight-lines, (2, 3))
+#test(block.text, "a\nb\nc")
+
+---
+// Ref: true
+// Test how hidden and highlighted lines render.
+#set page(width: 120pt)
+```rust:2..4{3}
+fn main() {
+    let x = 1;
+    let y = 2;
+}
+```
+
+#raw("a\nb\nc", block: true, highlight-lines: (1, 3))
+
+---
+// Malformed options are kept as text.
+#test(```rust:3 a```.text, ":3 a")
+#test(```rust{1,} a```.text, "{1,} a")
+
+---
+// Error: 36-40 array must contain exactly two entries
+#raw("a", block: true, line-range: (1,))
+
+---
+// Error: 39-45 line range must not end before it starts
+#raw("a\nb", block: true, line-range: (2, 1))
+
+---
+// Error: 39-45 line numbers must be at least 1
+#raw("a\nb", block: true, line-range: (0, 1))
+
+---
+// Error: 2-46 line range ends after the last line
+// Hint: 2-46 the raw text has 2 line(s)
+#raw("a\nb", block: true, line-range: (1, 3))
+
+---
+// Error: 2-51 cannot highlight line 3
+// Hint: 2-51 the raw text has 2 line(s)
+#raw("a\nb", block: true, highlight-lines: (1, 3))
+
+---
+// Error: 8-13 line range must not end before it starts
+```rust:2..1
+a
+b
+```
+
+---
+// Error: 8-14 highlighted line range must not end before it starts
+```rust{3..2}
+a
+b
+```
+
+---
+// Error: 1:1-4:4 cannot highlight line 3
+// Hint: 1:1-4:4 the raw text has 2 line(s)
+```rust{3}
+a
+b
+```
+
+---
+// Huge highlighted ranges are checked without expanding them.
+// Error: 1:1-4:4 cannot highlight line 3
+// Hint: 1:1-4:4 the raw text has 2 line(s)
+```rust{1..99999999999}
+a
+b
+```
+
+---
+// Error: 8-33 line number is too large
+```rust{1..99999999999999999999}
+a
+```
+
+---
+// Error: 8-32 line number is too large
+```rust:1..99999999999999999999
+a
+```
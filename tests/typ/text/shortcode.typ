@@ -0,0 +1,29 @@
+// Test emoji shortcodes.
+// Ref: false
+
+---
+#test([:face.grin:], [#emoji.face.grin])
+#test([:cat.face:], [#emoji.cat.face])
+
+---
+// Custom shortcodes shadow the built-in ones, which remain available.
+#let emoji = (party: "🎉", face: "F")
+#test([:party:], [🎉])
+#test([:face:], [F])
+#test([:cat.face:], [🐱])
+
+---
+// Colons within words and before spaces stay text.
+#test([12:30:45], [12\:30\:45])
+#test([a:b:c], [a\:b\:c])
+
+---
+// Unknown shortcodes are kept as text.
+// Warning: 8-14 unknown shortcode `TODO`
+// Hint: 8-14 it is kept as text, escape a colon to silence this warning
+#test([:TODO: fix].children.first().text, ":TODO:")
+
+---
+// Warning: 1-12 unknown shortcode `not.emoji`
+// Hint: 1-12 it is kept as text, escape a colon to silence this warning
+:not.emoji:
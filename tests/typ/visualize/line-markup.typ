@@ -0,0 +1,29 @@
+// Test horizontal rule markup. The rules are indented because unindented
+// dashes would separate the tests.
+// Ref: false
+
+---
+#let rule = [
+  -----
+].children.find(it => it.func() == line)
+#test(rule.length, 100%)
+
+---
+// Dashes next to other content are shorthands.
+#test([a ---], [a #sym.dash.em])
+#test([--- b], [#sym.dash.em b])
+
+---
+// Ref: true
+// Test that rules span the full width, also between lines of a paragraph,
+// while dashes within a line stay em dashes.
+#set page(width: 120pt)
+Above
+  ---
+Middle --- with a dash and a---b.
+
+  -----
+
+#set line(stroke: 2pt + maroon)
+Below
+  ---